@@ -1 +0,0 @@
-sqlite3 ./data/dotacord.db << ./sql/create_tables.sql
@@ -0,0 +1,314 @@
+---
+--- Schema as created by the hand-run scripts in sql/ before migrations existed.
+--- Every statement is idempotent so existing databases can adopt it.
+---
+
+---
+--- PLAYERS
+---
+
+CREATE TABLE IF NOT EXISTS players
+(
+    player_id INT PRIMARY KEY NOT NULL
+);
+
+---
+--- SERVERS
+---
+
+CREATE TABLE IF NOT EXISTS servers
+(
+    server_id     BIGINT PRIMARY KEY NOT NULL,
+    server_name   TEXT               NOT NULL,
+    channel_id    BIGINT             NULL,
+    is_sub_week   INTEGER            NOT NULL DEFAULT 0,
+    is_sub_month  INTEGER            NOT NULL DEFAULT 0,
+    is_sub_reload INTEGER            NOT NULL DEFAULT 0,
+    weekly_day      INTEGER            NULL,
+    weekly_hour     INTEGER            NULL,
+    monthly_week    INTEGER            NULL,
+    monthly_weekday INTEGER            NULL,
+    monthly_hour    INTEGER            NULL
+);
+
+---
+--- PLAYER_SERVERS
+---
+
+CREATE TABLE IF NOT EXISTS player_servers
+(
+    player_id       INT    NOT NULL,
+    server_id       BIGINT NOT NULL,
+    player_name     TEXT   NULL,
+    discord_user_id BIGINT NULL,
+    discord_name    TEXT   NOT NULL,
+
+    FOREIGN KEY (player_id) REFERENCES players (player_id),
+    FOREIGN KEY (server_id) REFERENCES servers (server_id)
+);
+
+---
+--- PLAYER_MATCHES
+---
+
+CREATE TABLE IF NOT EXISTS player_matches
+(
+    match_id    INTEGER NOT NULL,
+    player_id   INTEGER NOT NULL,
+    hero_id     INTEGER NOT NULL,
+    kills       INTEGER NOT NULL,
+    deaths      INTEGER NOT NULL,
+    assists     INTEGER NOT NULL,
+    rank        INTEGER NOT NULL,
+    party_size  INTEGER NOT NULL,
+    faction     INTEGER NOT NULL,
+    is_victory  INTEGER NOT NULL,
+    start_time  INTEGER NOT NULL,
+    duration    INTEGER NOT NULL,
+    game_mode   INTEGER NOT NULL,
+    lobby_type  INTEGER NOT NULL,
+
+    FOREIGN KEY (player_id) REFERENCES players (player_id)
+);
+
+---
+--- COMMAND_EVENTS
+---
+
+CREATE TABLE IF NOT EXISTS command_events
+(
+    event_id     INTEGER PRIMARY KEY AUTOINCREMENT NOT NULL,
+    server_id    BIGINT             NOT NULL,
+    event_type   TEXT               NOT NULL,
+    event_time   INTEGER            NOT NULL,
+    user_id      BIGINT             NOT NULL,
+
+    FOREIGN KEY (server_id) REFERENCES servers (server_id)
+);
+
+---
+--- HEROES
+---
+
+CREATE TABLE IF NOT EXISTS heroes (
+    hero_id     INTEGER PRIMARY KEY NOT NULL,
+    name        TEXT    NOT NULL,
+    is_carry    INTEGER NOT NULL DEFAULT 0,
+    is_mid      INTEGER NOT NULL DEFAULT 0,
+    is_offlane  INTEGER NOT NULL DEFAULT 0,
+    is_support  INTEGER NOT NULL DEFAULT 0
+);
+
+---
+--- HERO_NICKNAMES
+---
+
+CREATE TABLE IF NOT EXISTS hero_nicknames (
+    id          INTEGER PRIMARY KEY AUTOINCREMENT,
+    hero_id     INTEGER NOT NULL,
+    nickname    TEXT    NOT NULL COLLATE NOCASE,
+    FOREIGN KEY (hero_id) REFERENCES heroes(hero_id),
+    UNIQUE(hero_id, nickname)
+);
+
+---
+--- HERO_FACETS
+---
+
+CREATE TABLE IF NOT EXISTS hero_facets
+(
+    hero_id INTEGER NOT NULL,
+    variant INTEGER NOT NULL,
+    name    TEXT    NOT NULL,
+    title   TEXT    NOT NULL,
+
+    PRIMARY KEY (hero_id, variant),
+    FOREIGN KEY (hero_id) REFERENCES heroes (hero_id)
+);
+
+---
+--- PATCHES
+---
+
+CREATE TABLE IF NOT EXISTS patches
+(
+    patch_id     INTEGER PRIMARY KEY NOT NULL,
+    name         TEXT    NOT NULL,
+    release_time INTEGER NOT NULL
+);
+
+---
+--- CHAT_MESSAGES
+---
+
+CREATE TABLE IF NOT EXISTS chat_messages
+(
+    id                 INTEGER PRIMARY KEY AUTOINCREMENT NOT NULL,
+    conversation_id    BIGINT  NOT NULL,
+    discord_message_id BIGINT  NOT NULL UNIQUE,
+    channel_id         BIGINT  NOT NULL,
+    user_id            BIGINT  NOT NULL,
+    role               TEXT    NOT NULL,
+    content            TEXT    NOT NULL,
+    created_at         INTEGER NOT NULL
+);
+
+---
+--- PLAYER_RULES
+---
+
+CREATE TABLE IF NOT EXISTS player_rules
+(
+    id              INTEGER PRIMARY KEY AUTOINCREMENT NOT NULL,
+    server_id       BIGINT  NOT NULL,
+    discord_user_id BIGINT  NOT NULL,
+    rule_text       TEXT    NOT NULL,
+
+    FOREIGN KEY (server_id) REFERENCES servers (server_id)
+);
+
+---
+--- PLAYER_PROFILES
+---
+
+CREATE TABLE IF NOT EXISTS player_profiles
+(
+    player_id        BIGINT  PRIMARY KEY NOT NULL,
+    persona_name     TEXT    NULL,
+    avatar_url       TEXT    NULL,
+    rank_tier        INTEGER NULL,
+    leaderboard_rank INTEGER NULL,
+    last_login       INTEGER NULL,
+    updated_at       INTEGER NOT NULL,
+
+    FOREIGN KEY (player_id) REFERENCES players (player_id)
+);
+
+---
+--- RANK_HISTORY
+---
+
+CREATE TABLE IF NOT EXISTS rank_history
+(
+    id               INTEGER PRIMARY KEY AUTOINCREMENT,
+    player_id        BIGINT  NOT NULL,
+    rank_tier        INTEGER NULL,
+    leaderboard_rank INTEGER NULL,
+    recorded_at      INTEGER NOT NULL,
+
+    FOREIGN KEY (player_id) REFERENCES players (player_id)
+);
+
+CREATE INDEX IF NOT EXISTS idx_rank_history_player_recorded ON rank_history (player_id, recorded_at);
+
+---
+--- PLAYER_SYNC_STATES
+---
+
+CREATE TABLE IF NOT EXISTS player_sync_states
+(
+    player_id       BIGINT  PRIMARY KEY NOT NULL,
+    last_match_id   BIGINT  NOT NULL,
+    last_start_time INTEGER NOT NULL,
+    last_full_sync  INTEGER NULL,
+    updated_at      INTEGER NOT NULL,
+
+    FOREIGN KEY (player_id) REFERENCES players (player_id)
+);
+
+---
+--- MATCH_PLAYER_DETAILS
+---
+
+CREATE TABLE IF NOT EXISTS match_player_details
+(
+    match_id     BIGINT  NOT NULL,
+    player_id    BIGINT  NOT NULL,
+    gpm          INTEGER NULL,
+    xpm          INTEGER NULL,
+    last_hits    INTEGER NULL,
+    denies       INTEGER NULL,
+    hero_damage  INTEGER NULL,
+    tower_damage INTEGER NULL,
+    hero_healing INTEGER NULL,
+    net_worth    INTEGER NULL,
+    item_0       INTEGER NULL,
+    item_1       INTEGER NULL,
+    item_2       INTEGER NULL,
+    item_3       INTEGER NULL,
+    item_4       INTEGER NULL,
+    item_5       INTEGER NULL,
+    item_neutral INTEGER NULL,
+    is_parsed    INTEGER NOT NULL DEFAULT 0,
+    fetched_at   INTEGER NOT NULL,
+
+    PRIMARY KEY (match_id, player_id),
+    FOREIGN KEY (player_id) REFERENCES players (player_id)
+);
+
+---
+--- MATCH_INGEST_ERRORS
+---
+
+CREATE TABLE IF NOT EXISTS match_ingest_errors
+(
+    match_id      BIGINT  NOT NULL,
+    player_id     BIGINT  NOT NULL,
+    error_kind    TEXT    NOT NULL,
+    error_message TEXT    NOT NULL,
+    raw_json      TEXT    NOT NULL,
+    created_at    INTEGER NOT NULL,
+    updated_at    INTEGER NOT NULL,
+
+    PRIMARY KEY (match_id, player_id),
+    FOREIGN KEY (player_id) REFERENCES players (player_id)
+);
+
+---
+--- PARSE_JOBS
+---
+
+CREATE TABLE IF NOT EXISTS parse_jobs
+(
+    match_id     BIGINT  PRIMARY KEY NOT NULL,
+    job_id       BIGINT  NULL,
+    state        TEXT    NOT NULL,
+    attempts     INTEGER NOT NULL DEFAULT 0,
+    created_at   INTEGER NOT NULL,
+    updated_at   INTEGER NOT NULL,
+    last_error   TEXT    NULL
+);
+
+---
+--- EXCLUDED_MATCHES
+---
+
+CREATE TABLE IF NOT EXISTS excluded_matches
+(
+    id          INTEGER PRIMARY KEY AUTOINCREMENT,
+    match_id    BIGINT  NOT NULL,
+    -- NULL excludes the match for every server.
+    server_id   BIGINT  NULL,
+    reason      TEXT    NOT NULL,
+    -- Discord user id of the admin, NULL when excluded from the command line.
+    excluded_by BIGINT  NULL,
+    excluded_at INTEGER NOT NULL
+);
+
+CREATE INDEX IF NOT EXISTS idx_excluded_matches_match ON excluded_matches (match_id);
+
+-- Previously hard-coded in map_to_player_match.
+INSERT INTO excluded_matches (match_id, server_id, reason, excluded_by, excluded_at)
+SELECT 1439386853, NULL, 'Voided match', NULL, strftime('%s', 'now')
+WHERE NOT EXISTS (SELECT 1 FROM excluded_matches WHERE match_id = 1439386853 AND server_id IS NULL);
+
+---
+--- API_CACHE_ENTRIES
+---
+
+CREATE TABLE IF NOT EXISTS api_cache_entries
+(
+    cache_key  TEXT    PRIMARY KEY NOT NULL,
+    payload    TEXT    NOT NULL,
+    fetched_at INTEGER NOT NULL
+);
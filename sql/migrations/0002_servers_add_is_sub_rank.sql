@@ -0,0 +1 @@
+ALTER TABLE servers ADD COLUMN is_sub_rank INTEGER NOT NULL DEFAULT 0;
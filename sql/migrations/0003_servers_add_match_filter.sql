@@ -0,0 +1,2 @@
+ALTER TABLE servers ADD COLUMN game_modes TEXT NULL;
+ALTER TABLE servers ADD COLUMN lobby_types TEXT NULL;
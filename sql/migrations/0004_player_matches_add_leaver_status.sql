@@ -0,0 +1 @@
+ALTER TABLE player_matches ADD COLUMN leaver_status INTEGER NOT NULL DEFAULT 0;
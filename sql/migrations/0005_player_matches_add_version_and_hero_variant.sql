@@ -0,0 +1,2 @@
+ALTER TABLE player_matches ADD COLUMN version INTEGER NULL;
+ALTER TABLE player_matches ADD COLUMN hero_variant INTEGER NULL;
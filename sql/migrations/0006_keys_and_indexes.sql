@@ -0,0 +1,71 @@
+---
+--- PLAYER_MATCHES
+--- Rebuilt with the (match_id, player_id) key the entity declares. Duplicates keep the first row.
+---
+
+CREATE TABLE player_matches_new
+(
+    match_id      INTEGER NOT NULL,
+    player_id     INTEGER NOT NULL,
+    hero_id       INTEGER NOT NULL,
+    kills         INTEGER NOT NULL,
+    deaths        INTEGER NOT NULL,
+    assists       INTEGER NOT NULL,
+    rank          INTEGER NOT NULL,
+    party_size    INTEGER NOT NULL,
+    faction       INTEGER NOT NULL,
+    is_victory    INTEGER NOT NULL,
+    start_time    INTEGER NOT NULL,
+    duration      INTEGER NOT NULL,
+    game_mode     INTEGER NOT NULL,
+    lobby_type    INTEGER NOT NULL,
+    leaver_status INTEGER NOT NULL DEFAULT 0,
+    version       INTEGER NULL,
+    hero_variant  INTEGER NULL,
+
+    PRIMARY KEY (match_id, player_id),
+    FOREIGN KEY (player_id) REFERENCES players (player_id)
+);
+
+INSERT OR IGNORE INTO player_matches_new
+SELECT match_id, player_id, hero_id, kills, deaths, assists, rank, party_size, faction, is_victory,
+       start_time, duration, game_mode, lobby_type, leaver_status, version, hero_variant
+FROM player_matches
+ORDER BY rowid;
+
+DROP TABLE player_matches;
+ALTER TABLE player_matches_new RENAME TO player_matches;
+
+-- query_matches_by_duration and query_matches_since
+CREATE INDEX idx_player_matches_player_start ON player_matches (player_id, start_time);
+-- query_matches_by_hero
+CREATE INDEX idx_player_matches_player_hero ON player_matches (player_id, hero_id);
+
+---
+--- PLAYER_SERVERS
+--- Rebuilt with a (player_id, server_id) key. Duplicates keep the first registration.
+---
+
+CREATE TABLE player_servers_new
+(
+    player_id       INT    NOT NULL,
+    server_id       BIGINT NOT NULL,
+    player_name     TEXT   NULL,
+    discord_user_id BIGINT NULL,
+    discord_name    TEXT   NOT NULL,
+
+    PRIMARY KEY (player_id, server_id),
+    FOREIGN KEY (player_id) REFERENCES players (player_id),
+    FOREIGN KEY (server_id) REFERENCES servers (server_id)
+);
+
+INSERT OR IGNORE INTO player_servers_new
+SELECT player_id, server_id, player_name, discord_user_id, discord_name
+FROM player_servers
+ORDER BY rowid;
+
+DROP TABLE player_servers;
+ALTER TABLE player_servers_new RENAME TO player_servers;
+
+-- query_server_players and query_player_by_discord_user
+CREATE INDEX idx_player_servers_server_user ON player_servers (server_id, discord_user_id);
@@ -78,6 +78,7 @@ pub fn build_client(extra_context: &str) -> Result<Box<dyn LLMProvider>, Error>
         .function(tools::top_winrate_heroes_tool())
         .function(tools::get_global_hero_stats_tool())
         .function(tools::get_player_hero_stats_tool())
+        .function(tools::get_hero_matchups_tool())
         .function(tools::add_hero_nickname_tool())
         .function(tools::add_hero_role_tool())
         .function(tools::remove_hero_role_tool())
@@ -4,12 +4,15 @@ use chrono::{DateTime, Utc};
 use llm::builder::{FunctionBuilder, ParamBuilder};
 use llm::ToolCall;
 use serde::Serialize;
-use tracing::info;
+use tracing::{info, warn};
 
-use crate::api::{hero_stats_cache, open_dota_links};
-use crate::database::{heroes_db, player_matches_db, player_servers_db};
+use crate::api::open_dota_api::HeroStatBracket;
+use crate::api::{api_cache, api_wrapper, open_dota_links};
+use crate::database::player_profiles_db::{self, PlayerProfileModel};
+use crate::database::types::MatchFilter;
+use crate::database::{heroes_db, player_matches_db, player_servers_db, servers_db};
 use crate::leaderboard::duration::Duration;
-use crate::leaderboard::stats_calculator;
+use crate::leaderboard::{benchmarks, medal, stats_calculator};
 use crate::Error;
 
 const MAX_TOOL_ROUNDS: usize = 5;
@@ -19,6 +22,9 @@ pub struct ToolContext {
     pub max_recent_match_days: u64,
     pub max_recent_matches: usize,
     pub top_winrate_count: usize,
+    pub count_abandons_in_win_rates: bool,
+    /// Discord user who sent the message, used to default hero meta to their rank bracket.
+    pub requester_discord_id: i64,
 }
 
 pub fn max_tool_rounds() -> usize {
@@ -39,6 +45,25 @@ fn calc_pick_trend_pct(trend: &[i64]) -> f64 {
     }
 }
 
+/// `pub_pick_trend` covers every bracket, so it's left out when a single bracket is requested.
+fn bracket_pick_trend_pct(bracket: HeroStatBracket, trend: &[i64]) -> Option<f64> {
+    matches!(bracket, HeroStatBracket::All).then(|| calc_pick_trend_pct(trend))
+}
+
+fn bracket_param() -> ParamBuilder {
+    let mut brackets = vec!["All".to_string()];
+    brackets.extend(medal::MEDAL_NAMES.iter().map(|m| m.to_string()));
+    brackets.push("Turbo".to_string());
+
+    ParamBuilder::new("bracket")
+        .type_of("string")
+        .description(
+            "Rank bracket to read win rates from. \
+             Leave it out to use the asking player's own medal, or All when it isn't known.",
+        )
+        .enum_values(brackets)
+}
+
 pub fn get_recent_matches_tool() -> FunctionBuilder {
     FunctionBuilder::new("get_recent_matches")
         .description(
@@ -70,10 +95,12 @@ pub fn get_hero_by_nickname_tool() -> FunctionBuilder {
 pub fn top_winrate_heroes_tool() -> FunctionBuilder {
     FunctionBuilder::new("top_winrate_heroes")
         .description(
-            "Get the top Dota 2 heroes by overall win rate for a position. \
+            "Get the top Dota 2 heroes by win rate for a position within a rank bracket. \
              Returns heroes sorted by win rate descending. \
-             pick_trend_pct is the percent change in picks from the previous period to the current period.",
+             pick_trend_pct is the percent change in picks from the previous period to the current period, \
+             across all brackets, so it is null when a specific bracket is requested.",
         )
+        .param(bracket_param())
         .param(
             ParamBuilder::new("position")
                 .type_of("string")
@@ -92,7 +119,10 @@ pub fn get_player_hero_stats_tool() -> FunctionBuilder {
     FunctionBuilder::new("get_player_hero_stats")
         .description(
             "Get a player's stats on a specific Dota 2 hero. \
-             Returns their last 5 games on the hero, overall win rate, and total games played.",
+             Returns their last 5 games on the hero, overall win rate, total games played, \
+             games and win rate per facet, and benchmarks: the player's averages over their recent games \
+             with the global percentile for each (e.g. 72 means better than 72% of games on the hero). \
+             Use the facet breakdown when asked which facet is better.",
         )
         .param(
             ParamBuilder::new("username")
@@ -111,9 +141,11 @@ pub fn get_global_hero_stats_tool() -> FunctionBuilder {
     FunctionBuilder::new("get_global_hero_stats")
         .description(
             "Get the global win rate and position(s) for a specific Dota 2 hero. \
-             Use this when a user asks how a hero is performing overall. \
-             pick_trend_pct is the percent change in picks from the previous period to the current period.",
+             Use this when a user asks how a hero is performing overall or in a rank bracket. \
+             pick_trend_pct is the percent change in picks from the previous period to the current period, \
+             across all brackets, so it is null when a specific bracket is requested.",
         )
+        .param(bracket_param())
         .param(
             ParamBuilder::new("hero_name")
                 .type_of("string")
@@ -122,6 +154,33 @@ pub fn get_global_hero_stats_tool() -> FunctionBuilder {
         .required(vec!["hero_name".to_string()])
 }
 
+pub fn get_hero_matchups_tool() -> FunctionBuilder {
+    FunctionBuilder::new("get_hero_matchups")
+        .description(
+            "Get the heroes that counter a Dota 2 hero and the heroes it does well against. \
+             Use this when a user asks what counters a hero or what to pick against it. \
+             win_rate_pct is the given hero's win rate against each listed hero, \
+             so low values in countered_by are the strongest counters.",
+        )
+        .param(
+            ParamBuilder::new("hero_name")
+                .type_of("string")
+                .description("Hero name or nickname (e.g. 'Storm Spirit', 'Tree')"),
+        )
+        .param(
+            ParamBuilder::new("position")
+                .type_of("string")
+                .description("Only list heroes played in this position, e.g. for 'as a support'")
+                .enum_values(vec![
+                    "Carry".to_string(),
+                    "Mid".to_string(),
+                    "Offlane".to_string(),
+                    "Support".to_string(),
+                ]),
+        )
+        .required(vec!["hero_name".to_string()])
+}
+
 pub fn add_hero_nickname_tool() -> FunctionBuilder {
     FunctionBuilder::new("add_hero_nickname")
         .description("Add a nickname for a Dota 2 hero so it can be found by that name in future lookups.")
@@ -219,6 +278,7 @@ pub fn query_player_stats_tool() -> FunctionBuilder {
                     "Month".to_string(),
                     "Year".to_string(),
                     "AllTime".to_string(),
+                    "ThisPatch".to_string(),
                 ]),
         )
         .required(vec!["username".to_string(), "duration".to_string()])
@@ -232,8 +292,9 @@ pub async fn execute_tool(tool_call: &ToolCall, ctx: &ToolContext) -> Result<Str
         "get_match_details" => execute_get_match_details(&tool_call.function.arguments, ctx).await,
         "get_hero_by_nickname" => execute_get_hero_by_nickname(&tool_call.function.arguments).await,
         "top_winrate_heroes" => execute_top_winrate_heroes(&tool_call.function.arguments, ctx).await,
-        "get_global_hero_stats" => execute_get_global_hero_stats(&tool_call.function.arguments).await,
+        "get_global_hero_stats" => execute_get_global_hero_stats(&tool_call.function.arguments, ctx).await,
         "get_player_hero_stats" => execute_get_player_hero_stats(&tool_call.function.arguments, ctx).await,
+        "get_hero_matchups" => execute_get_hero_matchups(&tool_call.function.arguments, ctx).await,
         "add_hero_nickname" => execute_add_hero_nickname(&tool_call.function.arguments).await,
         "add_hero_role" => execute_add_hero_role(&tool_call.function.arguments).await,
         "remove_hero_role" => execute_remove_hero_role(&tool_call.function.arguments).await,
@@ -258,6 +319,7 @@ struct MatchSummary {
 #[derive(Serialize)]
 struct RecentMatchesResponse {
     player_name: String,
+    medal: String,
     matches: Vec<MatchSummary>,
     total_matches: usize,
 }
@@ -267,6 +329,17 @@ struct ErrorResponse {
     error: String,
 }
 
+/// Medal emoji plus name, e.g. `<:dota_ancient:..> Legend 3`, so replies can show it next to the player.
+fn medal_label(profile: Option<&PlayerProfileModel>) -> String {
+    let rank_tier = profile.and_then(|p| p.rank_tier);
+    let leaderboard_rank = profile.and_then(|p| p.leaderboard_rank);
+    format!(
+        "{} {}",
+        medal::medal_emoji(rank_tier),
+        medal::medal_name(rank_tier, leaderboard_rank)
+    )
+}
+
 async fn execute_get_recent_matches(arguments: &str, ctx: &ToolContext) -> Result<String, Error> {
     let args: serde_json::Value = serde_json::from_str(arguments)?;
     let username = args["username"]
@@ -292,12 +365,22 @@ async fn execute_get_recent_matches(arguments: &str, ctx: &ToolContext) -> Resul
         .clone()
         .unwrap_or_else(|| target.discord_name.clone());
 
+    let profile = player_profiles_db::query_profile(target.player_id).await?;
+    let medal = medal_label(profile.as_ref());
+
     let now = Utc::now();
     let start = now - chrono::Duration::days(ctx.max_recent_match_days as i64);
     let start_ts = start.timestamp() as i32;
     let end_ts = now.timestamp() as i32;
 
-    let matches = player_matches_db::query_matches_by_duration(target.player_id, start_ts, end_ts).await?;
+    let match_filter = servers_db::query_match_filter(ctx.server_id).await?;
+    let matches = player_matches_db::query_matches_by_duration(
+        target.player_id,
+        start_ts,
+        end_ts,
+        &match_filter,
+    )
+    .await?;
 
     let mut sorted_matches = matches;
     sorted_matches.sort_by(|a, b| b.start_time.cmp(&a.start_time));
@@ -306,6 +389,7 @@ async fn execute_get_recent_matches(arguments: &str, ctx: &ToolContext) -> Resul
     if sorted_matches.is_empty() {
         return Ok(serde_json::to_string(&RecentMatchesResponse {
             player_name: display_name,
+            medal,
             matches: vec![],
             total_matches: 0,
         })?);
@@ -321,6 +405,7 @@ async fn execute_get_recent_matches(arguments: &str, ctx: &ToolContext) -> Resul
         &server_players,
         start_ts,
         end_ts,
+        &match_filter,
     )
     .await?;
 
@@ -358,6 +443,7 @@ async fn execute_get_recent_matches(arguments: &str, ctx: &ToolContext) -> Resul
     let total = summaries.len();
     Ok(serde_json::to_string(&RecentMatchesResponse {
         player_name: display_name,
+        medal,
         matches: summaries,
         total_matches: total,
     })?)
@@ -402,6 +488,7 @@ async fn build_friends_map(
     server_players: &[player_servers_db::PlayerServerModel],
     start_ts: i32,
     end_ts: i32,
+    match_filter: &MatchFilter,
 ) -> Result<HashMap<i64, Vec<String>>, Error> {
     let mut friends_map: HashMap<i64, Vec<String>> = HashMap::new();
 
@@ -411,8 +498,13 @@ async fn build_friends_map(
         }
 
         let their_matches =
-            player_matches_db::query_matches_by_duration(player.player_id, start_ts, end_ts)
-                .await?;
+            player_matches_db::query_matches_by_duration(
+                player.player_id,
+                start_ts,
+                end_ts,
+                match_filter,
+            )
+            .await?;
 
         let friend_name = player
             .player_name
@@ -435,6 +527,7 @@ async fn build_friends_map(
 #[derive(Serialize)]
 struct MatchPlayerDetail {
     player_name: String,
+    medal: String,
     hero: String,
     kills: i32,
     deaths: i32,
@@ -458,6 +551,13 @@ async fn execute_get_match_details(arguments: &str, ctx: &ToolContext) -> Result
         .as_i64()
         .ok_or_else(|| Error::from("Missing 'match_id' parameter"))?;
 
+    let match_filter = servers_db::query_match_filter(ctx.server_id).await?;
+    if match_filter.excluded_match_ids.contains(&match_id) {
+        return Ok(serde_json::to_string(&ErrorResponse {
+            error: format!("Match {} has been excluded by an admin.", match_id),
+        })?);
+    }
+
     let match_records = player_matches_db::query_match_by_id(match_id).await?;
 
     if match_records.is_empty() {
@@ -482,6 +582,11 @@ async fn execute_get_match_details(arguments: &str, ctx: &ToolContext) -> Result
         })
         .collect();
 
+    let profiles = player_profiles_db::query_profiles_by_player_ids(
+        match_records.iter().map(|m| m.player_id).collect(),
+    )
+    .await?;
+
     let first = &match_records[0];
     let date = DateTime::from_timestamp(first.start_time, 0)
         .map(|dt| dt.format("%Y-%m-%d %H:%M UTC").to_string())
@@ -508,6 +613,7 @@ async fn execute_get_match_details(arguments: &str, ctx: &ToolContext) -> Result
 
             MatchPlayerDetail {
                 player_name,
+                medal: medal_label(profiles.get(&m.player_id)),
                 hero,
                 kills: m.kills,
                 deaths: m.deaths,
@@ -569,7 +675,13 @@ async fn execute_get_hero_by_nickname(arguments: &str) -> Result<String, Error>
 struct HeroWinRate {
     hero: String,
     win_rate_pct: f64,
-    pick_trend_pct: f64,
+    pick_trend_pct: Option<f64>,
+}
+
+#[derive(Serialize)]
+struct TopWinrateHeroesResponse {
+    bracket: String,
+    heroes: Vec<HeroWinRate>,
 }
 
 async fn execute_top_winrate_heroes(arguments: &str, ctx: &ToolContext) -> Result<String, Error> {
@@ -590,22 +702,27 @@ async fn execute_top_winrate_heroes(arguments: &str, ctx: &ToolContext) -> Resul
         }
     };
 
+    let bracket = match args["bracket"].as_str() {
+        Some(name) => match parse_bracket(name) {
+            Ok(bracket) => bracket,
+            Err(error) => return Ok(serde_json::to_string(&ErrorResponse { error })?),
+        },
+        None => requester_bracket(ctx).await?,
+    };
+
     let heroes = heroes_db::query_heroes_by_position(&position).await?;
     let hero_ids: std::collections::HashSet<i32> = heroes.iter().map(|h| h.hero_id).collect();
 
-    let stats = hero_stats_cache::get_hero_stats().await?;
+    let stats = api_cache::get_hero_stats().await?;
 
     let mut results: Vec<HeroWinRate> = stats
         .iter()
-        .filter(|s| hero_ids.contains(&s.id) && s.pub_pick >= 100)
+        .filter(|s| hero_ids.contains(&s.id) && s.picks_and_wins(bracket).0 >= 100)
         .map(|s| {
-            let win_rate_pct = if s.pub_pick > 0 {
-                ((s.pub_win as f64 / s.pub_pick as f64) * 1000.0).round() / 10.0
-            } else {
-                0.0
-            };
+            let (picks, wins) = s.picks_and_wins(bracket);
+            let win_rate_pct = ((wins as f64 / picks as f64) * 1000.0).round() / 10.0;
 
-            let pick_trend_pct = calc_pick_trend_pct(&s.pub_pick_trend);
+            let pick_trend_pct = bracket_pick_trend_pct(bracket, &s.pub_pick_trend);
 
             HeroWinRate {
                 hero: s.localized_name.clone(),
@@ -618,22 +735,34 @@ async fn execute_top_winrate_heroes(arguments: &str, ctx: &ToolContext) -> Resul
     results.sort_by(|a, b| b.win_rate_pct.partial_cmp(&a.win_rate_pct).unwrap_or(std::cmp::Ordering::Equal));
     results.truncate(ctx.top_winrate_count);
 
-    Ok(serde_json::to_string(&results)?)
+    Ok(serde_json::to_string(&TopWinrateHeroesResponse {
+        bracket: bracket_label(bracket),
+        heroes: results,
+    })?)
 }
 
 #[derive(Serialize)]
 struct GlobalHeroStatsResponse {
     hero: String,
+    bracket: String,
+    picks: i64,
     win_rate_pct: f64,
-    pick_trend_pct: f64,
+    pick_trend_pct: Option<f64>,
     positions: Vec<String>,
 }
 
-async fn execute_get_global_hero_stats(arguments: &str) -> Result<String, Error> {
+async fn execute_get_global_hero_stats(arguments: &str, ctx: &ToolContext) -> Result<String, Error> {
     let args: serde_json::Value = serde_json::from_str(arguments)?;
     let hero_name = args["hero_name"]
         .as_str()
         .ok_or_else(|| Error::from("Missing 'hero_name' parameter"))?;
+    let bracket = match args["bracket"].as_str() {
+        Some(name) => match parse_bracket(name) {
+            Ok(bracket) => bracket,
+            Err(error) => return Ok(serde_json::to_string(&ErrorResponse { error })?),
+        },
+        None => requester_bracket(ctx).await?,
+    };
 
     let hero_lookup = heroes_db::HeroLookup::load().await?;
 
@@ -643,7 +772,7 @@ async fn execute_get_global_hero_stats(arguments: &str) -> Result<String, Error>
         })?);
     };
 
-    let stats = hero_stats_cache::get_hero_stats().await?;
+    let stats = api_cache::get_hero_stats().await?;
 
     let Some(stat) = stats.iter().find(|s| s.id == hero.hero_id) else {
         return Ok(serde_json::to_string(&ErrorResponse {
@@ -651,13 +780,14 @@ async fn execute_get_global_hero_stats(arguments: &str) -> Result<String, Error>
         })?);
     };
 
-    let win_rate_pct = if stat.pub_pick > 0 {
-        ((stat.pub_win as f64 / stat.pub_pick as f64) * 1000.0).round() / 10.0
+    let (picks, wins) = stat.picks_and_wins(bracket);
+    let win_rate_pct = if picks > 0 {
+        ((wins as f64 / picks as f64) * 1000.0).round() / 10.0
     } else {
         0.0
     };
 
-    let pick_trend_pct = calc_pick_trend_pct(&stat.pub_pick_trend);
+    let pick_trend_pct = bracket_pick_trend_pct(bracket, &stat.pub_pick_trend);
 
     let mut positions = Vec::new();
     if hero.is_carry { positions.push("Carry".to_string()); }
@@ -667,6 +797,8 @@ async fn execute_get_global_hero_stats(arguments: &str) -> Result<String, Error>
 
     Ok(serde_json::to_string(&GlobalHeroStatsResponse {
         hero: hero.name.clone(),
+        bracket: bracket_label(bracket),
+        picks,
         win_rate_pct,
         pick_trend_pct,
         positions,
@@ -682,14 +814,33 @@ struct PlayerHeroMatch {
     assists: i32,
     date: String,
     duration_minutes: i32,
+    facet: Option<String>,
+}
+
+#[derive(Serialize)]
+struct PlayerHeroFacetStats {
+    facet: String,
+    games: usize,
+    win_rate_pct: f64,
+}
+
+#[derive(Serialize)]
+struct PlayerHeroBenchmark {
+    stat: String,
+    average: f64,
+    percentile: f64,
+    games: usize,
 }
 
 #[derive(Serialize)]
 struct PlayerHeroStatsResponse {
     player_name: String,
+    medal: String,
     hero: String,
     total_games: usize,
     win_rate_pct: f64,
+    facets: Vec<PlayerHeroFacetStats>,
+    benchmarks: Vec<PlayerHeroBenchmark>,
     recent_matches: Vec<PlayerHeroMatch>,
 }
 
@@ -728,7 +879,11 @@ async fn execute_get_player_hero_stats(arguments: &str, ctx: &ToolContext) -> Re
         .clone()
         .unwrap_or_else(|| target.discord_name.clone());
 
-    let matches = player_matches_db::query_matches_by_hero(target.player_id, hero.hero_id).await?;
+    let profile = player_profiles_db::query_profile(target.player_id).await?;
+    let match_filter = servers_db::query_match_filter(ctx.server_id).await?;
+    let matches =
+        player_matches_db::query_matches_by_hero(target.player_id, hero.hero_id, &match_filter)
+            .await?;
 
     let total_games = matches.len();
     let wins = matches.iter().filter(|m| m.is_victory).count();
@@ -738,6 +893,58 @@ async fn execute_get_player_hero_stats(arguments: &str, ctx: &ToolContext) -> Re
         0.0
     };
 
+    let facet_titles: HashMap<i32, String> = heroes_db::query_facets(hero.hero_id)
+        .await?
+        .into_iter()
+        .map(|f| (f.variant, f.title))
+        .collect();
+    let facet_title = |variant: Option<i32>| -> Option<String> {
+        variant.map(|v| {
+            facet_titles
+                .get(&v)
+                .cloned()
+                .unwrap_or_else(|| format!("Facet {}", v))
+        })
+    };
+
+    // Matches from before facets existed (or from sources without them) have no variant
+    let mut facet_counts: HashMap<Option<i32>, (usize, usize)> = HashMap::new();
+    for m in &matches {
+        let entry = facet_counts.entry(m.hero_variant).or_default();
+        entry.0 += 1;
+        if m.is_victory {
+            entry.1 += 1;
+        }
+    }
+    let mut facets: Vec<PlayerHeroFacetStats> = facet_counts
+        .into_iter()
+        .map(|(variant, (games, wins))| PlayerHeroFacetStats {
+            facet: facet_title(variant).unwrap_or_else(|| "Unknown".to_string()),
+            games,
+            win_rate_pct: ((wins as f64 / games as f64) * 1000.0).round() / 10.0,
+        })
+        .collect();
+    facets.sort_by(|a, b| b.games.cmp(&a.games));
+
+    // Benchmarks need OpenDota; the rest of the answer comes from the database and still stands.
+    let benchmark_stats =
+        match benchmarks::hero_benchmarks(hero.hero_id, target.player_id, &matches).await {
+            Ok(stats) => stats,
+            Err(e) => {
+                warn!(hero_id = hero.hero_id, error = %e, "Failed to load hero benchmarks");
+                vec![]
+            }
+        };
+    let benchmarks: Vec<PlayerHeroBenchmark> = benchmark_stats
+        .into_iter()
+        .map(|b| PlayerHeroBenchmark {
+            stat: b.label.to_string(),
+            average: b.average,
+            percentile: b.percentile,
+            games: b.games,
+        })
+        .collect();
+
     let recent_matches: Vec<PlayerHeroMatch> = matches
         .iter()
         .take(5)
@@ -754,19 +961,78 @@ async fn execute_get_player_hero_stats(arguments: &str, ctx: &ToolContext) -> Re
                 assists: m.assists,
                 date,
                 duration_minutes: m.duration / 60,
+                facet: facet_title(m.hero_variant),
             }
         })
         .collect();
 
     Ok(serde_json::to_string(&PlayerHeroStatsResponse {
         player_name: display_name,
+        medal: medal_label(profile.as_ref()),
         hero: hero.name.clone(),
         total_games,
         win_rate_pct,
+        facets,
+        benchmarks,
         recent_matches,
     })?)
 }
 
+#[derive(Serialize)]
+struct HeroMatchupResponse {
+    hero: String,
+    games: i64,
+    win_rate_pct: f64,
+}
+
+#[derive(Serialize)]
+struct HeroMatchupsResponse {
+    hero: String,
+    position: Option<String>,
+    countered_by: Vec<HeroMatchupResponse>,
+    strong_against: Vec<HeroMatchupResponse>,
+}
+
+async fn execute_get_hero_matchups(arguments: &str, ctx: &ToolContext) -> Result<String, Error> {
+    let args: serde_json::Value = serde_json::from_str(arguments)?;
+    let hero_name = args["hero_name"]
+        .as_str()
+        .ok_or_else(|| Error::from("Missing 'hero_name' parameter"))?;
+    let position = match args["position"].as_str() {
+        Some(position_str) => match parse_position(position_str) {
+            Ok(position) => Some(position),
+            Err(error) => return Ok(serde_json::to_string(&ErrorResponse { error })?),
+        },
+        None => None,
+    };
+
+    let hero_lookup = heroes_db::HeroLookup::load().await?;
+    let Some(hero) = hero_lookup.find_by_name(hero_name) else {
+        return Ok(serde_json::to_string(&ErrorResponse {
+            error: format!("No hero found matching '{}'.", hero_name),
+        })?);
+    };
+
+    let matchups =
+        api_wrapper::hero_matchups(hero.hero_id, position, ctx.top_winrate_count).await?;
+    let to_response = |list: Vec<api_wrapper::HeroMatchup>| -> Vec<HeroMatchupResponse> {
+        list.into_iter()
+            .map(|m| HeroMatchupResponse {
+                hero: m.hero.name,
+                games: m.games,
+                win_rate_pct: m.win_rate_pct,
+            })
+            .collect()
+    };
+
+    Ok(serde_json::to_string(&HeroMatchupsResponse {
+        hero: hero.name.clone(),
+        position: position.map(|p| format!("{:?}", p)),
+        countered_by: to_response(matchups.worst),
+        strong_against: to_response(matchups.best),
+    })?)
+}
+
 async fn execute_add_hero_nickname(arguments: &str) -> Result<String, Error> {
     let args: serde_json::Value = serde_json::from_str(arguments)?;
     let hero_name = args["hero_name"]
@@ -789,6 +1055,47 @@ async fn execute_add_hero_nickname(arguments: &str) -> Result<String, Error> {
     Ok("{\"success\": true}".to_string())
 }
 
+fn parse_bracket(bracket_str: &str) -> Result<HeroStatBracket, String> {
+    match bracket_str {
+        "All" => Ok(HeroStatBracket::All),
+        "Turbo" => Ok(HeroStatBracket::Turbo),
+        _ => medal::MEDAL_NAMES
+            .iter()
+            .position(|m| *m == bracket_str)
+            .map(|i| HeroStatBracket::Medal(i as i32 + 1))
+            .ok_or_else(|| {
+                format!(
+                    "Invalid bracket '{}'. Use All, Turbo, or a medal (Herald to Immortal).",
+                    bracket_str
+                )
+            }),
+    }
+}
+
+fn bracket_label(bracket: HeroStatBracket) -> String {
+    match bracket {
+        HeroStatBracket::All => "All".to_string(),
+        HeroStatBracket::Turbo => "Turbo".to_string(),
+        HeroStatBracket::Medal(m) => medal::MEDAL_NAMES[m as usize - 1].to_string(),
+    }
+}
+
+/// The requester's medal when they're registered on this server and calibrated, else `All`.
+async fn requester_bracket(ctx: &ToolContext) -> Result<HeroStatBracket, Error> {
+    let server_players = player_servers_db::query_server_players(ctx.server_id).await?;
+    let Some(player) = server_players
+        .iter()
+        .find(|p| p.discord_user_id == Some(ctx.requester_discord_id))
+    else {
+        return Ok(HeroStatBracket::All);
+    };
+
+    let profile = player_profiles_db::query_profile(player.player_id).await?;
+    Ok(medal::medal_of(profile.and_then(|p| p.rank_tier))
+        .map(HeroStatBracket::Medal)
+        .unwrap_or(HeroStatBracket::All))
+}
+
 fn parse_position(position_str: &str) -> Result<heroes_db::Position, String> {
     match position_str {
         "Carry" => Ok(heroes_db::Position::Carry),
@@ -861,6 +1168,8 @@ struct PlayerStatsOverall {
     ranked_matches: i32,
     ranked_wins: i32,
     ranked_winrate_pct: f64,
+    matches_played: i32,
+    abandons: i32,
     most_played_hero: MostPlayedHero,
 }
 
@@ -903,6 +1212,7 @@ struct PlayerStatsSingleMatch {
 #[derive(Serialize)]
 struct PlayerStatsResponse {
     player_name: String,
+    medal: String,
     duration: String,
     overall: PlayerStatsOverall,
     single_match: PlayerStatsSingleMatch,
@@ -915,8 +1225,9 @@ fn parse_duration(duration_str: &str) -> Result<Duration, String> {
         "Month" => Ok(Duration::Month),
         "Year" => Ok(Duration::Year),
         "AllTime" => Ok(Duration::AllTime),
+        "ThisPatch" => Ok(Duration::ThisPatch),
         _ => Err(format!(
-            "Invalid duration '{}'. Use Day, Week, Month, Year, or AllTime.",
+            "Invalid duration '{}'. Use Day, Week, Month, Year, AllTime, or ThisPatch.",
             duration_str
         )),
     }
@@ -964,11 +1275,18 @@ async fn execute_query_player_stats(arguments: &str, ctx: &ToolContext) -> Resul
         .unwrap_or_else(|| target.discord_name.clone());
 
     let now = Utc::now();
-    let start = duration.start_date(now);
+    let start = duration.start_date(now).await?;
     let start_ts = start.timestamp() as i32;
     let end_ts = now.timestamp() as i32;
 
-    let matches = player_matches_db::query_matches_by_duration(target.player_id, start_ts, end_ts).await?;
+    let match_filter = servers_db::query_match_filter(ctx.server_id).await?;
+    let matches = player_matches_db::query_matches_by_duration(
+        target.player_id,
+        start_ts,
+        end_ts,
+        &match_filter,
+    )
+    .await?;
 
     if matches.is_empty() {
         return Ok(serde_json::to_string(&ErrorResponse {
@@ -980,10 +1298,13 @@ async fn execute_query_player_stats(arguments: &str, ctx: &ToolContext) -> Resul
         })?);
     }
 
+    let profile = player_profiles_db::query_profile(target.player_id).await?;
     let stats = stats_calculator::player_matches_to_stats(
         &matches,
         target.player_id,
         display_name.clone(),
+        profile.as_ref().and_then(|p| p.rank_tier),
+        ctx.count_abandons_in_win_rates,
     )?;
 
     let hero_lookup = heroes_db::HeroLookup::load().await?;
@@ -1002,6 +1323,8 @@ async fn execute_query_player_stats(arguments: &str, ctx: &ToolContext) -> Resul
         ranked_matches: stats.ranked_stats.total_matches,
         ranked_wins: stats.ranked_stats.wins,
         ranked_winrate_pct: calc_winrate_pct(stats.ranked_stats.wins, stats.ranked_stats.total_matches),
+        matches_played: stats.matches_played,
+        abandons: stats.abandons,
         most_played_hero: MostPlayedHero {
             hero: hero_name(most_played.hero_id),
             matches: most_played.stats.total_matches,
@@ -1037,6 +1360,7 @@ async fn execute_query_player_stats(arguments: &str, ctx: &ToolContext) -> Resul
 
     Ok(serde_json::to_string(&PlayerStatsResponse {
         player_name: display_name,
+        medal: medal_label(profile.as_ref()),
         duration: duration.to_label().to_string(),
         overall,
         single_match,
@@ -0,0 +1,251 @@
+use std::collections::HashMap;
+use std::future::Future;
+use std::sync::{Arc, OnceLock};
+
+use chrono::Utc;
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+use tokio::sync::{Mutex, RwLock};
+use tracing::{info, warn};
+
+use super::match_source::{self, MatchSource};
+use super::open_dota_api::{ApiBenchmarks, ApiHeroMatchup, ApiHeroStat, ApiPlayer};
+use crate::database::api_cache_db::{self, ApiCacheEntryModel};
+use crate::Error;
+
+/// How long an entry is served as-is, and how long after that it may still be served
+/// while a background refresh runs. Past both, callers wait for the refresh and only
+/// fall back to the old entry if it fails.
+#[derive(Debug, Clone, Copy)]
+pub struct CachePolicy {
+    pub ttl_secs: i64,
+    pub max_stale_secs: i64,
+}
+
+pub const HERO_STATS: CachePolicy = CachePolicy {
+    ttl_secs: 3600,
+    max_stale_secs: 24 * 3600,
+};
+
+pub const HERO_MATCHUPS: CachePolicy = CachePolicy {
+    ttl_secs: 12 * 3600,
+    max_stale_secs: 7 * 24 * 3600,
+};
+
+/// Only for lookups that can live with an old profile, the scheduled sync uses `fetch_player`.
+pub const PLAYER_PROFILE: CachePolicy = CachePolicy {
+    ttl_secs: 30 * 60,
+    max_stale_secs: 7 * 24 * 3600,
+};
+
+pub const BENCHMARKS: CachePolicy = CachePolicy {
+    ttl_secs: 24 * 3600,
+    max_stale_secs: 7 * 24 * 3600,
+};
+
+#[derive(Clone)]
+struct CachedEntry {
+    payload: Arc<str>,
+    fetched_at: i64,
+}
+
+impl CachedEntry {
+    fn age(&self) -> i64 {
+        Utc::now().timestamp() - self.fetched_at
+    }
+
+    fn decode<T: DeserializeOwned>(&self, key: &str) -> Result<T, Error> {
+        serde_json::from_str(&self.payload)
+            .map_err(|e| format!("Invalid cache entry '{}': {}", key, e).into())
+    }
+}
+
+static ENTRIES: OnceLock<RwLock<HashMap<String, CachedEntry>>> = OnceLock::new();
+static KEY_LOCKS: OnceLock<Mutex<HashMap<String, Arc<Mutex<()>>>>> = OnceLock::new();
+
+fn entries() -> &'static RwLock<HashMap<String, CachedEntry>> {
+    ENTRIES.get_or_init(|| RwLock::new(HashMap::new()))
+}
+
+/// One lock per key, held for the duration of a fetch so concurrent callers share it.
+async fn key_lock(key: &str) -> Arc<Mutex<()>> {
+    let mut locks = KEY_LOCKS.get_or_init(|| Mutex::new(HashMap::new())).lock().await;
+    locks.entry(key.to_string()).or_default().clone()
+}
+
+pub async fn get_hero_stats() -> Result<Vec<ApiHeroStat>, Error> {
+    // Versioned since entries stored before the bracket fields existed decode with zero picks.
+    get_or_fetch("heroStats/v2", HERO_STATS, || async {
+        match_source::source().hero_stats().await
+    })
+    .await
+}
+
+pub async fn get_hero_matchups(hero_id: i32) -> Result<Vec<ApiHeroMatchup>, Error> {
+    get_or_fetch(&format!("heroes/{hero_id}/matchups"), HERO_MATCHUPS, move || async move {
+        match_source::source().hero_matchups(hero_id).await
+    })
+    .await
+}
+
+pub async fn get_hero_benchmarks(hero_id: i32) -> Result<ApiBenchmarks, Error> {
+    get_or_fetch(&format!("benchmarks/{hero_id}"), BENCHMARKS, move || async move {
+        match_source::source().hero_benchmarks(hero_id).await
+    })
+    .await
+}
+
+pub async fn get_player(
+    source: &'static dyn MatchSource,
+    player_id: i64,
+) -> Result<ApiPlayer, Error> {
+    get_or_fetch(&player_key(source, player_id), PLAYER_PROFILE, move || async move {
+        source.player(player_id).await
+    })
+    .await
+}
+
+/// Always fetches and never falls back to a stale entry, since the result is stored as current.
+/// The cache is still updated so `get_player` benefits.
+pub async fn fetch_player(
+    source: &'static dyn MatchSource,
+    player_id: i64,
+) -> Result<ApiPlayer, Error> {
+    let key = player_key(source, player_id);
+    let lock = key_lock(&key).await;
+    let _guard = lock.lock().await;
+
+    let player = source.player(player_id).await?;
+    store(&key, &player).await?;
+    Ok(player)
+}
+
+/// Keyed by source too, since servers can use different sources.
+fn player_key(source: &dyn MatchSource, player_id: i64) -> String {
+    format!("players/{player_id}/{}", source.name())
+}
+
+/// Returns the cached value for `key`, fetching it when missing or too stale. Entries are kept
+/// in memory and in SQLite, so they survive restarts.
+pub async fn get_or_fetch<T, F, Fut>(key: &str, policy: CachePolicy, fetch: F) -> Result<T, Error>
+where
+    T: Serialize + DeserializeOwned + Send + 'static,
+    F: FnOnce() -> Fut + Send + 'static,
+    Fut: Future<Output = Result<T, Error>> + Send + 'static,
+{
+    if let Some(entry) = load_entry(key).await? {
+        let age = entry.age();
+        if age < policy.ttl_secs {
+            return entry.decode(key);
+        }
+        if age < policy.ttl_secs + policy.max_stale_secs {
+            spawn_refresh(key, fetch).await;
+            return entry.decode(key);
+        }
+    }
+
+    refresh(key, policy, fetch).await
+}
+
+async fn load_entry(key: &str) -> Result<Option<CachedEntry>, Error> {
+    if let Some(entry) = entries().read().await.get(key) {
+        return Ok(Some(entry.clone()));
+    }
+
+    let Some(row) = api_cache_db::query_entry(key).await? else {
+        return Ok(None);
+    };
+    let entry = CachedEntry {
+        payload: row.payload.into(),
+        fetched_at: row.fetched_at,
+    };
+    entries().write().await.insert(key.to_string(), entry.clone());
+    Ok(Some(entry))
+}
+
+async fn store<T: Serialize>(key: &str, value: &T) -> Result<(), Error> {
+    let payload = serde_json::to_string(value)?;
+    let fetched_at = Utc::now().timestamp();
+
+    entries().write().await.insert(
+        key.to_string(),
+        CachedEntry {
+            payload: payload.as_str().into(),
+            fetched_at,
+        },
+    );
+
+    // The in-memory copy still serves this process if the write fails
+    if let Err(e) = api_cache_db::upsert_entry(ApiCacheEntryModel {
+        cache_key: key.to_string(),
+        payload,
+        fetched_at,
+    })
+    .await
+    {
+        warn!(key, error = ?e, "Failed to persist cache entry");
+    }
+    Ok(())
+}
+
+/// Fetches under the key lock. Callers that queued behind another fetch reuse its result.
+async fn refresh<T, F, Fut>(key: &str, policy: CachePolicy, fetch: F) -> Result<T, Error>
+where
+    T: Serialize + DeserializeOwned,
+    F: FnOnce() -> Fut,
+    Fut: Future<Output = Result<T, Error>>,
+{
+    let lock = key_lock(key).await;
+    let _guard = lock.lock().await;
+
+    let stale = load_entry(key).await?;
+    if let Some(entry) = stale.as_ref().filter(|e| e.age() < policy.ttl_secs) {
+        return entry.decode(key);
+    }
+
+    info!(key, "Cache miss or stale, fetching");
+    match fetch().await {
+        Ok(value) => {
+            store(key, &value).await?;
+            Ok(value)
+        }
+        Err(e) => match stale {
+            Some(entry) => {
+                warn!(
+                    key,
+                    age_secs = entry.age(),
+                    error = ?e,
+                    "Fetch failed, serving stale cache entry"
+                );
+                entry.decode(key)
+            }
+            None => Err(e),
+        },
+    }
+}
+
+/// Refreshes in the background unless a fetch for `key` is already running.
+async fn spawn_refresh<T, F, Fut>(key: &str, fetch: F)
+where
+    T: Serialize + Send + 'static,
+    F: FnOnce() -> Fut + Send + 'static,
+    Fut: Future<Output = Result<T, Error>> + Send + 'static,
+{
+    let Ok(guard) = key_lock(key).await.try_lock_owned() else {
+        return;
+    };
+
+    let key = key.to_string();
+    tokio::spawn(async move {
+        let _guard = guard;
+        info!(key = key.as_str(), "Cache entry stale, refreshing in background");
+        match fetch().await {
+            Ok(value) => {
+                if let Err(e) = store(&key, &value).await {
+                    warn!(key = key.as_str(), error = ?e, "Failed to store refreshed cache entry");
+                }
+            }
+            Err(e) => warn!(key = key.as_str(), error = ?e, "Background cache refresh failed"),
+        }
+    });
+}
@@ -1,42 +1,276 @@
-use tracing::info;
+use std::collections::{HashMap, HashSet};
+use std::sync::Arc;
 
-use crate::api::open_dota_api;
-use crate::database::{database_access, heroes_db, player_matches_db, player_servers_db};
+use chrono::{DateTime, Utc};
+use sea_orm::DatabaseTransaction;
+use tokio::sync::Semaphore;
+use tokio::task::JoinSet;
+use tracing::{error, info, warn};
+
+use crate::api::{api_cache, match_source};
+use crate::api::open_dota_api::{self, PlayerMatchesQuery};
+use crate::database::{
+    database_access, excluded_matches_db, heroes_db, match_details_db, match_ingest_errors_db,
+    patches_db, player_matches_db, player_profiles_db, player_servers_db, player_sync_db,
+    rank_history_db,
+};
 use crate::Error;
 
+const SECONDS_PER_DAY: i64 = 86_400;
+/// Extra days fetched on top of the gap since the last seen match, to cover late-arriving matches.
+const INCREMENTAL_OVERLAP_DAYS: i64 = 1;
+const INCREMENTAL_LIMIT: u32 = 100;
+/// Profiles and ranks change rarely, so they're refreshed at most this often during reloads.
+const PROFILE_SYNC_INTERVAL_SECS: i64 = 3600;
+/// Matchups with fewer games than this are too noisy to suggest.
+const MIN_MATCHUP_GAMES: i64 = 20;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SyncMode {
+    /// Only fetch matches newer than the last synced match.
+    Incremental,
+    /// Re-download the full match history.
+    Full,
+}
+
 pub struct ReloadPlayerStat {
     pub player_id: i64,
     pub display_name: String,
     pub result: Result<Option<usize>, String>,
 }
 
-/// Reloads matches for a given player from the OpenDota API and inserts any new matches into the database.
+/// Pulls the hero catalog so heroes from a new patch are known before their matches arrive,
+/// along with their facets and the patch list. Returns how many heroes were added.
 #[tracing::instrument(level = "trace")]
-pub async fn reload_player(player: &player_servers_db::PlayerServerModel) -> ReloadPlayerStat {
-    info!(player_id = player.player_id, "Reloading matches for player");
+pub async fn sync_hero_catalog() -> Result<usize, Error> {
+    let source = match_source::source();
+    let api_heroes = source.heroes().await?;
+    let added = heroes_db::sync_heroes(&api_heroes).await?;
+
+    for hero in &added {
+        info!(hero_id = hero.hero_id, name = hero.name.as_str(), "Added new hero to catalog");
+    }
+    info!(total = api_heroes.len(), added = added.len(), "Synced hero catalog");
+
+    let abilities = source.hero_abilities().await?;
+    let facets = heroes_db::sync_facets(&api_heroes, &abilities).await?;
+    info!(facets, "Synced hero facets");
+
+    let patches = patches_db::sync_patches(&source.patches().await?).await?;
+    info!(patches, "Synced patch list");
+
+    Ok(added.len())
+}
+
+/// How the queried hero fares against `hero`.
+#[derive(Clone)]
+pub struct HeroMatchup {
+    pub hero: heroes_db::HeroModel,
+    pub games: i64,
+    pub win_rate_pct: f64,
+}
+
+/// Best and worst matchups for a hero, each sorted from the most extreme win rate.
+pub struct HeroMatchups {
+    pub best: Vec<HeroMatchup>,
+    /// The heroes that counter the queried hero.
+    pub worst: Vec<HeroMatchup>,
+}
+
+/// Ranks the hero's matchups, skipping opponents with too few games to judge.
+/// With a `position`, only opponents flagged for it are listed.
+pub async fn hero_matchups(
+    hero_id: i32,
+    position: Option<heroes_db::Position>,
+    count: usize,
+) -> Result<HeroMatchups, Error> {
+    let api_matchups = api_cache::get_hero_matchups(hero_id).await?;
+    let hero_lookup = heroes_db::HeroLookup::load().await?;
+
+    let mut matchups: Vec<HeroMatchup> = api_matchups
+        .into_iter()
+        .filter(|m| m.games_played >= MIN_MATCHUP_GAMES)
+        .filter_map(|m| {
+            let hero = hero_lookup.get(m.hero_id)?;
+            if position.is_some_and(|p| !p.applies_to(hero)) {
+                return None;
+            }
+            Some(HeroMatchup {
+                hero: hero.clone(),
+                games: m.games_played,
+                win_rate_pct: ((m.wins as f64 / m.games_played as f64) * 1000.0).round() / 10.0,
+            })
+        })
+        .collect();
+
+    // With fewer than `2 * count` opponents the lists split them, so no hero appears in both.
+    matchups.sort_by(|a, b| b.win_rate_pct.total_cmp(&a.win_rate_pct));
+    let worst_count = count.min(matchups.len().div_ceil(2));
+    let best_count = count.min(matchups.len() - worst_count);
+    let mut worst = matchups.split_off(matchups.len() - worst_count);
+    worst.reverse();
+    matchups.truncate(best_count);
+
+    Ok(HeroMatchups {
+        best: matchups,
+        worst,
+    })
+}
+
+/// Reloads run concurrently, at most `concurrency` at a time. Requests still go through the shared
+/// OpenDota rate limiter, so the width only overlaps waiting on responses and the database.
+pub struct ParallelReload {
+    tasks: JoinSet<(usize, ReloadPlayerStat)>,
+}
+
+impl ParallelReload {
+    pub fn spawn(
+        players: Vec<player_servers_db::PlayerServerModel>,
+        mode: SyncMode,
+        concurrency: usize,
+    ) -> Self {
+        let permits = Arc::new(Semaphore::new(concurrency.max(1)));
+        let mut tasks = JoinSet::new();
+
+        for (index, player) in players.into_iter().enumerate() {
+            let permits = permits.clone();
+            tasks.spawn(async move {
+                // The semaphore is never closed, so acquiring can't fail.
+                let _permit = permits.acquire_owned().await.ok();
+                (index, reload_player(&player, mode).await)
+            });
+        }
+
+        Self { tasks }
+    }
+
+    /// The next reload to finish, with the player's index in the original list.
+    pub async fn next(&mut self) -> Option<(usize, ReloadPlayerStat)> {
+        loop {
+            match self.tasks.join_next().await? {
+                Ok(done) => return Some(done),
+                Err(e) => error!(error = ?e, "Player reload task panicked"),
+            }
+        }
+    }
+
+    /// Waits for every reload and returns the stats in the original player order.
+    pub async fn collect(mut self) -> Vec<ReloadPlayerStat> {
+        let mut done = Vec::new();
+        while let Some(stat) = self.next().await {
+            done.push(stat);
+        }
+        done.sort_by_key(|(index, _)| *index);
+        done.into_iter().map(|(_, stat)| stat).collect()
+    }
+}
+
+/// Reloads matches for a given player from the configured match source and inserts any new matches into the database.
+/// Incremental reloads fall back to a full sync when the player has never been synced.
+#[tracing::instrument(level = "trace")]
+pub async fn reload_player(
+    player: &player_servers_db::PlayerServerModel,
+    mode: SyncMode,
+) -> ReloadPlayerStat {
+    info!(player_id = player.player_id, ?mode, "Reloading matches for player");
 
     let result = async {
-        let db_matches = player_matches_db::query_matches_by_player_id(player.player_id).await?;
-        let api_matches = open_dota_api::get_player_matches(player.player_id).await?;
+        let now = Utc::now().timestamp();
+        let sync_state = match mode {
+            SyncMode::Incremental => player_sync_db::query_sync_state(player.player_id).await?,
+            SyncMode::Full => None,
+        };
+
+        let source = match_source::source_for_server(player.server_id);
+        let incremental = match &sync_state {
+            Some(state) => {
+                let days = (now - state.last_start_time).max(0) / SECONDS_PER_DAY
+                    + 1
+                    + INCREMENTAL_OVERLAP_DAYS;
+                let query = PlayerMatchesQuery {
+                    days: Some(days),
+                    limit: Some(INCREMENTAL_LIMIT),
+                };
+                let api_matches = source.player_matches(player.player_id, query).await?;
+                if api_matches.len() >= INCREMENTAL_LIMIT as usize {
+                    // The window holds more games than one page, so older ones would be skipped.
+                    info!(
+                        player_id = player.player_id,
+                        days,
+                        "Incremental sync hit the match limit, falling back to a full sync"
+                    );
+                    None
+                } else {
+                    let since = now - days * SECONDS_PER_DAY;
+                    Some((
+                        player_matches_db::query_matches_since(player.player_id, since).await?,
+                        api_matches,
+                    ))
+                }
+            }
+            None => None,
+        };
+        let is_full_sync = incremental.is_none();
+        let (db_matches, api_matches) = match incremental {
+            Some(matches) => matches,
+            None => (
+                player_matches_db::query_matches_by_player_id(player.player_id).await?,
+                source
+                    .player_matches(player.player_id, PlayerMatchesQuery::default())
+                    .await?,
+            ),
+        };
 
         info!(
             player_id = player.player_id,
+            is_full_sync,
             db_matches = db_matches.len(),
             api_matches = api_matches.len(),
-            "Fetched matches from OpenDota API"
+            "Fetched matches from match source"
         );
 
+        // A failed profile lookup shouldn't fail the match reload.
+        if let Err(e) = sync_player_profile(player, now).await {
+            warn!(player_id = player.player_id, error = %e, "Failed to sync player profile");
+        }
+
         if api_matches.is_empty() {
+            // No new games since the last sync is the common case for incremental reloads.
+            if !is_full_sync {
+                return Ok(Some(0));
+            }
             info!(
                 player_id = player.player_id,
                 server_id = player.server_id,
-                "No matches found from OpenDota API. Player may need to be removed."
+                "No matches found from match source. Player may need to be removed."
             );
             return Ok(None);
         }
 
+        // Fetches above run in parallel, writes go one player at a time.
+        let _write_guard = database_access::lock_writes().await;
         let match_count = insert_new_matches(player.player_id, &db_matches, &api_matches).await?;
 
+        let newest = api_matches
+            .iter()
+            .filter_map(|m| m.start_time_seconds.map(|t| (t, m.match_id)))
+            .max();
+        if let Some((last_start_time, last_match_id)) = newest {
+            let is_newer = sync_state
+                .as_ref()
+                .is_some_and(|s| last_start_time > s.last_start_time);
+            if is_full_sync || is_newer {
+                player_sync_db::upsert_sync_state(
+                    player.player_id,
+                    last_match_id,
+                    last_start_time,
+                    is_full_sync,
+                    now,
+                )
+                .await?;
+            }
+        }
+
         info!(
             player_id = player.player_id,
             matches_inserted = match_count,
@@ -58,6 +292,58 @@ pub async fn reload_player(player: &player_servers_db::PlayerServerModel) -> Rel
     }
 }
 
+/// Stores persona, avatar and rank from `/players/{id}` if the stored profile is older than `PROFILE_SYNC_INTERVAL_SECS`.
+async fn sync_player_profile(
+    player: &player_servers_db::PlayerServerModel,
+    now: i64,
+) -> Result<(), Error> {
+    let existing = player_profiles_db::query_profile(player.player_id).await?;
+    if existing
+        .as_ref()
+        .is_some_and(|p| now - p.updated_at < PROFILE_SYNC_INTERVAL_SECS)
+    {
+        return Ok(());
+    }
+
+    let source = match_source::source_for_server(player.server_id);
+    let api_player = api_cache::fetch_player(source, player.player_id).await?;
+    let profile = api_player.profile.as_ref();
+
+    let _write_guard = database_access::lock_writes().await;
+    player_profiles_db::upsert_profile(player_profiles_db::PlayerProfileModel {
+        player_id: player.player_id,
+        persona_name: profile.and_then(|p| p.personaname.clone()),
+        avatar_url: profile.and_then(|p| p.avatarfull.clone()),
+        rank_tier: api_player.rank_tier,
+        leaderboard_rank: api_player.leaderboard_rank,
+        last_login: profile
+            .and_then(|p| p.last_login.as_deref())
+            .and_then(|s| DateTime::parse_from_rfc3339(s).ok())
+            .map(|dt| dt.timestamp()),
+        updated_at: now,
+    })
+    .await?;
+
+    // The first sync seeds the history, so later changes have something to compare against.
+    let previous_rank_tier = existing.map(|p| p.rank_tier);
+    if previous_rank_tier != Some(api_player.rank_tier) {
+        rank_history_db::insert_rank(
+            player.player_id,
+            api_player.rank_tier,
+            api_player.leaderboard_rank,
+            now,
+        )
+        .await?;
+    }
+
+    info!(
+        player_id = player.player_id,
+        rank_tier = api_player.rank_tier,
+        "Synced player profile"
+    );
+    Ok(())
+}
+
 #[tracing::instrument(level = "trace", skip(db_matches, api_matches))]
 async fn insert_new_matches(
     player_id: i64,
@@ -65,24 +351,174 @@ async fn insert_new_matches(
     api_matches: &[open_dota_api::ApiPlayerMatch],
 ) -> Result<usize, Error> {
     let hero_lookup = heroes_db::HeroLookup::load().await?;
-    let mut player_match_count = 0;
+    let excluded = excluded_matches_db::query_global_excluded_ids().await?;
     let txn = database_access::get_transaction().await?;
 
+    // Sources can return the same match more than once, e.g. when providers are combined.
+    let mut seen: HashSet<i64> = db_matches.iter().map(|m| m.match_id).collect();
+    seen.extend(&excluded);
+    let new_matches = api_matches.iter().filter(|m| seen.insert(m.match_id));
+    let counts = import_matches(&txn, player_id, new_matches, &hero_lookup).await?;
+
+    txn.commit().await?;
+
+    if counts.quarantined > 0 {
+        warn!(
+            player_id,
+            quarantined = counts.quarantined,
+            "Some matches could not be mapped and were recorded as ingest errors"
+        );
+    }
+    Ok(counts.inserted)
+}
+
+struct ImportCounts {
+    inserted: usize,
+    quarantined: usize,
+}
+
+/// Maps and inserts each match. Matches that fail to map are recorded in `match_ingest_errors`
+/// with their raw JSON instead of failing the batch; errors for matches that now map are cleared.
+async fn import_matches<'a>(
+    txn: &DatabaseTransaction,
+    player_id: i64,
+    api_matches: impl Iterator<Item = &'a open_dota_api::ApiPlayerMatch>,
+    hero_lookup: &heroes_db::HeroLookup,
+) -> Result<ImportCounts, Error> {
+    let now = Utc::now().timestamp();
+    let mut counts = ImportCounts {
+        inserted: 0,
+        quarantined: 0,
+    };
+    let mut resolved = Vec::new();
+
     for api_match in api_matches {
-        if db_matches.iter().any(|m| m.match_id == api_match.match_id) {
-            continue;
+        match player_matches_db::map_to_player_match(api_match, player_id, hero_lookup) {
+            Ok(Some(player_match)) => {
+                if player_matches_db::insert_player_match(txn, player_match).await? {
+                    counts.inserted += 1;
+                }
+                resolved.push(api_match.match_id);
+            }
+            Ok(None) => resolved.push(api_match.match_id),
+            Err(e) => {
+                warn!(player_id, match_id = api_match.match_id, error = %e, "Failed to map match");
+                match_ingest_errors_db::upsert_error(
+                    txn,
+                    match_ingest_errors_db::MatchIngestErrorModel {
+                        match_id: api_match.match_id,
+                        player_id,
+                        error_kind: e.kind().to_string(),
+                        error_message: e.to_string(),
+                        raw_json: api_match.raw_json()?,
+                        created_at: now,
+                        updated_at: now,
+                    },
+                )
+                .await?;
+                counts.quarantined += 1;
+            }
+        }
+    }
+
+    match_ingest_errors_db::delete_errors(txn, player_id, resolved).await?;
+    Ok(counts)
+}
+
+pub struct RetryIngestStat {
+    pub imported: usize,
+    pub still_failing: usize,
+}
+
+/// Re-maps quarantined matches from their stored JSON, e.g. after a mapper fix or hero catalog sync.
+#[tracing::instrument(level = "trace")]
+pub async fn retry_ingest_errors(player_ids: Vec<i64>) -> Result<RetryIngestStat, Error> {
+    let errors = match_ingest_errors_db::query_errors_by_player_ids(player_ids).await?;
+    let hero_lookup = heroes_db::HeroLookup::load().await?;
+    let excluded = excluded_matches_db::query_global_excluded_ids().await?;
+
+    let mut by_player: HashMap<i64, Vec<open_dota_api::ApiPlayerMatch>> = HashMap::new();
+    for error in &errors {
+        let parsed = serde_json::from_str(&error.raw_json)
+            .and_then(open_dota_api::ApiPlayerMatch::from_raw);
+        match parsed {
+            Ok(api_match) => by_player.entry(error.player_id).or_default().push(api_match),
+            Err(e) => warn!(match_id = error.match_id, error = %e, "Stored match JSON is unreadable"),
         }
+    }
+
+    let mut stat = RetryIngestStat {
+        imported: 0,
+        still_failing: 0,
+    };
+    for (player_id, api_matches) in by_player {
+        let mut resolved: HashSet<i64> = player_matches_db::query_matches_by_player_id(player_id)
+            .await?
+            .into_iter()
+            .map(|m| m.match_id)
+            .collect();
+        resolved.extend(&excluded);
+
+        let txn = database_access::get_transaction().await?;
+        // Matches imported or excluded since they were quarantined only need their error cleared.
+        let already_resolved: Vec<i64> = api_matches
+            .iter()
+            .map(|m| m.match_id)
+            .filter(|id| resolved.contains(id))
+            .collect();
+        match_ingest_errors_db::delete_errors(&txn, player_id, already_resolved).await?;
+
+        let pending = api_matches.iter().filter(|m| !resolved.contains(&m.match_id));
+        let counts = import_matches(&txn, player_id, pending, &hero_lookup).await?;
+        txn.commit().await?;
 
-        let Some(player_match) =
-            player_matches_db::map_to_player_match(api_match, player_id, &hero_lookup)?
-        else {
-            continue;
+        stat.imported += counts.inserted;
+        stat.still_failing += counts.quarantined;
+    }
+
+    info!(
+        imported = stat.imported,
+        still_failing = stat.still_failing,
+        "Retried match ingest errors"
+    );
+    Ok(stat)
+}
+
+/// Fetches `/matches/{match_id}` for up to `batch_size` stored matches that have no details yet.
+/// Progress is tracked by the rows in `match_player_details`, so an interrupted run resumes where it stopped.
+/// Returns the number of matches processed.
+#[tracing::instrument(level = "trace")]
+pub async fn ingest_match_details(batch_size: usize) -> Result<usize, Error> {
+    let pending = match_details_db::query_pending_matches(batch_size).await?;
+    if pending.is_empty() {
+        return Ok(0);
+    }
+
+    info!(matches = pending.len(), "Ingesting match details");
+
+    let mut processed = 0;
+    for pending_match in pending {
+        let match_id = pending_match.match_id;
+        // Unknown matches still get (empty) rows so they aren't retried forever.
+        let api_details = match match_source::source().match_details(match_id).await {
+            Ok(details) => details,
+            Err(e) => {
+                warn!(match_id, error = %e, "Failed to fetch match details, will retry next batch");
+                continue;
+            }
         };
 
-        player_matches_db::insert_player_match(&txn, player_match).await?;
-        player_match_count += 1;
+        let details = match_details_db::map_match_details(
+            api_details.as_ref(),
+            match_id,
+            &pending_match.players,
+            Utc::now().timestamp(),
+        );
+
+        match_details_db::insert_details(details).await?;
+        processed += 1;
     }
 
-    txn.commit().await?;
-    Ok(player_match_count)
+    info!(processed, "Finished ingesting match details");
+    Ok(processed)
 }
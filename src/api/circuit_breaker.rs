@@ -0,0 +1,103 @@
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+use chrono::Utc;
+use tracing::{info, warn};
+
+use crate::config::CircuitBreakerConfig;
+
+pub(crate) enum Admission {
+    Allowed,
+    /// The circuit is open but a probe is due; the request goes out and its result decides.
+    Probe,
+    /// The circuit is open. Holds the unix time it opened.
+    Rejected { since: i64 },
+}
+
+struct CircuitState {
+    consecutive_failures: u32,
+    /// Unix time of the failure that opened the circuit.
+    opened_at: Option<i64>,
+    next_probe: Instant,
+}
+
+/// Stops sending requests to an API that keeps failing. After `failure_threshold` outage failures
+/// in a row, requests are rejected without going out, except for one probe per `probe_interval`.
+/// A successful request closes the circuit again.
+pub(crate) struct CircuitBreaker {
+    name: &'static str,
+    failure_threshold: u32,
+    probe_interval: Duration,
+    state: Mutex<CircuitState>,
+}
+
+impl CircuitBreaker {
+    pub(crate) fn new(name: &'static str, config: &CircuitBreakerConfig) -> Self {
+        Self {
+            name,
+            failure_threshold: config.failure_threshold.max(1),
+            probe_interval: Duration::from_secs(config.probe_interval_secs),
+            state: Mutex::new(CircuitState {
+                consecutive_failures: 0,
+                opened_at: None,
+                next_probe: Instant::now(),
+            }),
+        }
+    }
+
+    /// Only one caller gets `Probe` per interval, so a cancelled probe can't keep the circuit stuck.
+    pub(crate) fn admit(&self) -> Admission {
+        let mut state = self.state.lock().expect("circuit breaker lock poisoned");
+        let Some(since) = state.opened_at else {
+            return Admission::Allowed;
+        };
+
+        let now = Instant::now();
+        if now >= state.next_probe {
+            state.next_probe = now + self.probe_interval;
+            info!(api = self.name, "Circuit open, probing");
+            Admission::Probe
+        } else {
+            Admission::Rejected { since }
+        }
+    }
+
+    pub(crate) fn record_success(&self) {
+        let mut state = self.state.lock().expect("circuit breaker lock poisoned");
+        if let Some(since) = state.opened_at.take() {
+            info!(
+                api = self.name,
+                outage_secs = Utc::now().timestamp() - since,
+                "Circuit closed, API is responding again"
+            );
+        }
+        state.consecutive_failures = 0;
+    }
+
+    pub(crate) fn record_failure(&self) {
+        let mut state = self.state.lock().expect("circuit breaker lock poisoned");
+        state.consecutive_failures = state.consecutive_failures.saturating_add(1);
+        state.next_probe = Instant::now() + self.probe_interval;
+
+        if state.opened_at.is_none() && state.consecutive_failures >= self.failure_threshold {
+            state.opened_at = Some(Utc::now().timestamp());
+            warn!(
+                api = self.name,
+                failures = state.consecutive_failures,
+                probe_interval_secs = self.probe_interval.as_secs(),
+                "Circuit opened after repeated failures"
+            );
+        }
+    }
+
+    /// Whether a request would go out right now, either because the circuit is closed or a probe is due.
+    pub(crate) fn is_available(&self) -> bool {
+        let state = self.state.lock().expect("circuit breaker lock poisoned");
+        state.opened_at.is_none() || Instant::now() >= state.next_probe
+    }
+
+    /// Unix time the circuit opened, while it is open.
+    pub(crate) fn open_since(&self) -> Option<i64> {
+        self.state.lock().expect("circuit breaker lock poisoned").opened_at
+    }
+}
@@ -0,0 +1,141 @@
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+
+use chrono::Utc;
+use poise::serenity_prelude::async_trait;
+use serde::de::DeserializeOwned;
+use tracing::debug;
+
+use super::match_source::MatchSource;
+use super::open_dota_api::{
+    ApiBenchmarks, ApiHero, ApiHeroAbilities, ApiHeroMatchup, ApiHeroStat, ApiMatchDetails,
+    ApiPatch, ApiPlayer, ApiPlayerMatch, PlayerMatchesQuery, RawPlayerMatch,
+};
+use crate::Error;
+
+const SECONDS_PER_DAY: i64 = 86_400;
+
+/// Serves recorded OpenDota responses from disk. Files mirror the endpoint paths:
+///
+/// - `players/{player_id}/matches.json`
+/// - `players/{player_id}.json`
+/// - `matches/{match_id}.json`
+/// - `heroStats.json`
+/// - `heroes.json`
+/// - `heroes/{hero_id}/matchups.json`
+/// - `benchmarks/{hero_id}.json` (for `/benchmarks?hero_id=`)
+/// - `constants/hero_abilities.json`
+/// - `constants/patch.json`
+pub struct FixtureSource {
+    dir: PathBuf,
+}
+
+impl FixtureSource {
+    pub fn new(dir: &Path) -> Result<Self, Error> {
+        let dir = dir.to_path_buf();
+        if !dir.is_dir() {
+            return Err(format!("Fixture directory does not exist: {}", dir.display()).into());
+        }
+        Ok(Self { dir })
+    }
+
+    fn path(&self, endpoint: &str) -> PathBuf {
+        self.dir.join(format!("{endpoint}.json"))
+    }
+
+    async fn read<T: DeserializeOwned>(&self, path: &Path) -> Result<Option<T>, Error> {
+        debug!(path = %path.display(), "Reading fixture");
+        let contents = match tokio::fs::read_to_string(path).await {
+            Ok(contents) => contents,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(None),
+            Err(e) => return Err(format!("Failed to read fixture '{}': {}", path.display(), e).into()),
+        };
+        let value = serde_json::from_str(&contents)
+            .map_err(|e| format!("Invalid fixture '{}': {}", path.display(), e))?;
+        Ok(Some(value))
+    }
+}
+
+#[async_trait]
+impl MatchSource for FixtureSource {
+    fn name(&self) -> &'static str {
+        "fixture"
+    }
+
+    /// Applies `days` and `limit` like OpenDota does, so incremental reloads behave the same offline.
+    async fn player_matches(
+        &self,
+        player_id: i64,
+        query: PlayerMatchesQuery,
+    ) -> Result<Vec<ApiPlayerMatch>, Error> {
+        let path = self.path(&format!("players/{player_id}/matches"));
+        let mut matches: Vec<ApiPlayerMatch> = self
+            .read::<Vec<RawPlayerMatch>>(&path)
+            .await?
+            .unwrap_or_default()
+            .into_iter()
+            .map(|m| m.0)
+            .collect();
+
+        if let Some(days) = query.days {
+            let since = Utc::now().timestamp() - days * SECONDS_PER_DAY;
+            matches.retain(|m| m.start_time_seconds.is_some_and(|t| t >= since));
+        }
+        matches.sort_by(|a, b| b.start_time_seconds.cmp(&a.start_time_seconds));
+        if let Some(limit) = query.limit {
+            matches.truncate(limit as usize);
+        }
+
+        Ok(matches)
+    }
+
+    async fn hero_stats(&self) -> Result<Vec<ApiHeroStat>, Error> {
+        let path = self.path("heroStats");
+        self.read(&path)
+            .await?
+            .ok_or_else(|| format!("Missing fixture: {}", path.display()).into())
+    }
+
+    async fn heroes(&self) -> Result<Vec<ApiHero>, Error> {
+        let path = self.path("heroes");
+        self.read(&path)
+            .await?
+            .ok_or_else(|| format!("Missing fixture: {}", path.display()).into())
+    }
+
+    async fn hero_matchups(&self, hero_id: i32) -> Result<Vec<ApiHeroMatchup>, Error> {
+        let path = self.path(&format!("heroes/{hero_id}/matchups"));
+        Ok(self.read(&path).await?.unwrap_or_default())
+    }
+
+    async fn hero_benchmarks(&self, hero_id: i32) -> Result<ApiBenchmarks, Error> {
+        let path = self.path(&format!("benchmarks/{hero_id}"));
+        Ok(self.read(&path).await?.unwrap_or(ApiBenchmarks {
+            hero_id,
+            result: HashMap::new(),
+        }))
+    }
+
+    async fn hero_abilities(&self) -> Result<HashMap<String, ApiHeroAbilities>, Error> {
+        let path = self.path("constants/hero_abilities");
+        Ok(self.read(&path).await?.unwrap_or_default())
+    }
+
+    async fn patches(&self) -> Result<Vec<ApiPatch>, Error> {
+        let path = self.path("constants/patch");
+        Ok(self.read(&path).await?.unwrap_or_default())
+    }
+
+    async fn player(&self, player_id: i64) -> Result<ApiPlayer, Error> {
+        let path = self.path(&format!("players/{player_id}"));
+        Ok(self.read(&path).await?.unwrap_or(ApiPlayer {
+            profile: None,
+            rank_tier: None,
+            leaderboard_rank: None,
+        }))
+    }
+
+    async fn match_details(&self, match_id: i64) -> Result<Option<ApiMatchDetails>, Error> {
+        self.read(&self.path(&format!("matches/{match_id}"))).await
+    }
+}
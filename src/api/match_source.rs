@@ -0,0 +1,223 @@
+use std::collections::{HashMap, HashSet};
+use std::sync::{Arc, OnceLock};
+
+use poise::serenity_prelude::async_trait;
+use tracing::{info, warn};
+
+use super::fixture_source::FixtureSource;
+use super::open_dota_api::{
+    ApiBenchmarks, ApiHero, ApiHeroAbilities, ApiHeroMatchup, ApiHeroStat, ApiMatchDetails,
+    ApiPatch, ApiPlayer, ApiPlayerMatch, OpenDotaSource, PlayerMatchesQuery,
+};
+use super::stratz_source::StratzSource;
+use crate::config::{MatchSourceConfig, MatchSourceKind};
+use crate::Error;
+
+/// Where match, hero and profile data comes from. Responses use the OpenDota shapes,
+/// so every source can be fed through the same mappers.
+#[async_trait]
+pub trait MatchSource: Send + Sync {
+    fn name(&self) -> &'static str;
+
+    async fn player_matches(
+        &self,
+        player_id: i64,
+        query: PlayerMatchesQuery,
+    ) -> Result<Vec<ApiPlayerMatch>, Error>;
+
+    async fn hero_stats(&self) -> Result<Vec<ApiHeroStat>, Error>;
+
+    /// The hero catalog, used to pick up heroes added in a patch.
+    async fn heroes(&self) -> Result<Vec<ApiHero>, Error>;
+
+    async fn hero_matchups(&self, hero_id: i32) -> Result<Vec<ApiHeroMatchup>, Error>;
+
+    async fn hero_benchmarks(&self, hero_id: i32) -> Result<ApiBenchmarks, Error>;
+
+    /// Facets per hero, keyed by internal hero name.
+    async fn hero_abilities(&self) -> Result<HashMap<String, ApiHeroAbilities>, Error>;
+
+    async fn patches(&self) -> Result<Vec<ApiPatch>, Error>;
+
+    async fn player(&self, player_id: i64) -> Result<ApiPlayer, Error>;
+
+    /// Returns `None` when the source doesn't know the match.
+    async fn match_details(&self, match_id: i64) -> Result<Option<ApiMatchDetails>, Error>;
+}
+
+/// Player matches from both sources, de-duplicated on `match_id`. OpenDota wins ties since it
+/// carries fields STRATZ doesn't (e.g. `version`); STRATZ fills in matches OpenDota hasn't seen yet.
+struct CombinedSource {
+    primary: Arc<dyn MatchSource>,
+    secondary: Arc<dyn MatchSource>,
+}
+
+#[async_trait]
+impl MatchSource for CombinedSource {
+    fn name(&self) -> &'static str {
+        "combined"
+    }
+
+    async fn player_matches(
+        &self,
+        player_id: i64,
+        query: PlayerMatchesQuery,
+    ) -> Result<Vec<ApiPlayerMatch>, Error> {
+        let mut matches = self.primary.player_matches(player_id, query).await?;
+        // The secondary source only fills gaps, so its outages shouldn't fail the reload.
+        let secondary = match self.secondary.player_matches(player_id, query).await {
+            Ok(secondary) => secondary,
+            Err(e) => {
+                warn!(
+                    player_id,
+                    source = self.secondary.name(),
+                    error = %e,
+                    "Secondary source failed, using primary matches only"
+                );
+                return Ok(matches);
+            }
+        };
+
+        let seen: HashSet<i64> = matches.iter().map(|m| m.match_id).collect();
+        let before = matches.len();
+        matches.extend(secondary.into_iter().filter(|m| !seen.contains(&m.match_id)));
+
+        info!(
+            player_id,
+            primary = before,
+            added = matches.len() - before,
+            "Merged player matches from combined source"
+        );
+        Ok(matches)
+    }
+
+    async fn hero_stats(&self) -> Result<Vec<ApiHeroStat>, Error> {
+        self.primary.hero_stats().await
+    }
+
+    async fn heroes(&self) -> Result<Vec<ApiHero>, Error> {
+        self.primary.heroes().await
+    }
+
+    async fn hero_matchups(&self, hero_id: i32) -> Result<Vec<ApiHeroMatchup>, Error> {
+        self.primary.hero_matchups(hero_id).await
+    }
+
+    async fn hero_benchmarks(&self, hero_id: i32) -> Result<ApiBenchmarks, Error> {
+        self.primary.hero_benchmarks(hero_id).await
+    }
+
+    async fn hero_abilities(&self) -> Result<HashMap<String, ApiHeroAbilities>, Error> {
+        self.primary.hero_abilities().await
+    }
+
+    async fn patches(&self) -> Result<Vec<ApiPatch>, Error> {
+        self.primary.patches().await
+    }
+
+    async fn player(&self, player_id: i64) -> Result<ApiPlayer, Error> {
+        self.primary.player(player_id).await
+    }
+
+    async fn match_details(&self, match_id: i64) -> Result<Option<ApiMatchDetails>, Error> {
+        self.primary.match_details(match_id).await
+    }
+}
+
+struct SourceRegistry {
+    default: Arc<dyn MatchSource>,
+    server_overrides: HashMap<i64, Arc<dyn MatchSource>>,
+}
+
+static SOURCES: OnceLock<SourceRegistry> = OnceLock::new();
+
+/// Builds each configured kind once, so servers sharing a kind also share its rate limiter.
+fn build_source(
+    kind: MatchSourceKind,
+    config: &MatchSourceConfig,
+    built: &mut HashMap<MatchSourceKind, Arc<dyn MatchSource>>,
+) -> Result<Arc<dyn MatchSource>, Error> {
+    if let Some(source) = built.get(&kind) {
+        return Ok(source.clone());
+    }
+
+    let source: Arc<dyn MatchSource> = match kind {
+        MatchSourceKind::OpenDota => Arc::new(OpenDotaSource),
+        MatchSourceKind::Stratz => {
+            let stratz = config
+                .stratz
+                .as_ref()
+                .ok_or("[match_source.stratz] is required for the stratz source")?;
+            Arc::new(StratzSource::new(stratz)?)
+        }
+        MatchSourceKind::Combined => Arc::new(CombinedSource {
+            primary: build_source(MatchSourceKind::OpenDota, config, built)?,
+            secondary: build_source(MatchSourceKind::Stratz, config, built)?,
+        }),
+        MatchSourceKind::Fixture => {
+            let dir = config
+                .fixture_dir
+                .as_ref()
+                .ok_or("match_source.fixture_dir is required when kind = \"fixture\"")?;
+            Arc::new(FixtureSource::new(dir)?)
+        }
+    };
+
+    built.insert(kind, source.clone());
+    Ok(source)
+}
+
+pub fn init_source(config: &MatchSourceConfig) -> Result<(), Error> {
+    let mut built = HashMap::new();
+    let default = build_source(config.kind, config, &mut built)?;
+
+    let mut server_overrides = HashMap::new();
+    for server_override in &config.server_overrides {
+        let source = build_source(server_override.kind, config, &mut built)?;
+        info!(
+            server_id = server_override.server_id,
+            source = source.name(),
+            "Match source override configured"
+        );
+        server_overrides.insert(server_override.server_id, source);
+    }
+
+    info!(source = default.name(), "Match source initialized");
+
+    SOURCES
+        .set(SourceRegistry {
+            default,
+            server_overrides,
+        })
+        .map_err(|_already| {
+            Box::new(std::io::Error::new(
+                std::io::ErrorKind::AlreadyExists,
+                "Match source already initialized",
+            )) as Error
+        })?;
+
+    Ok(())
+}
+
+/// Defaults to OpenDota when `init_source` was never called.
+fn registry() -> &'static SourceRegistry {
+    SOURCES.get_or_init(|| SourceRegistry {
+        default: Arc::new(OpenDotaSource),
+        server_overrides: HashMap::new(),
+    })
+}
+
+/// The globally configured source.
+pub fn source() -> &'static dyn MatchSource {
+    registry().default.as_ref()
+}
+
+/// The source configured for `server_id`, falling back to the global one.
+pub fn source_for_server(server_id: i64) -> &'static dyn MatchSource {
+    let registry = registry();
+    registry
+        .server_overrides
+        .get(&server_id)
+        .unwrap_or(&registry.default)
+        .as_ref()
+}
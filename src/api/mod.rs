@@ -1,4 +1,10 @@
+pub mod api_cache;
 pub mod api_wrapper;
-pub mod hero_stats_cache;
+pub mod circuit_breaker;
+pub mod fixture_source;
+pub mod match_source;
 pub mod open_dota_api;
+pub mod open_dota_client;
 pub mod open_dota_links;
+pub mod parse_requests;
+pub mod stratz_source;
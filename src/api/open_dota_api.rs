@@ -1,10 +1,17 @@
-use serde::Deserialize;
+use std::collections::HashMap;
+
+use poise::serenity_prelude::async_trait;
+use reqwest::StatusCode;
+use serde::de::Error as _;
+use serde::{Deserialize, Deserializer, Serialize};
 use tracing::info;
 
-const BASE_URL: &str = "https://api.opendota.com/api";
+use super::match_source::MatchSource;
+use super::open_dota_client::{self, OpenDotaError};
+use crate::Error;
 
 #[allow(dead_code)]
-#[derive(Debug, Clone, Deserialize)]
+#[derive(Debug, Clone, Deserialize, Serialize)]
 pub struct ApiPlayerMatch {
     pub match_id: i64,
     pub player_slot: Option<i32>,
@@ -24,21 +31,88 @@ pub struct ApiPlayerMatch {
     pub leaver_status: Option<i32>,
     pub party_size: Option<i32>,
     pub hero_variant: Option<i32>,
+    /// The response object this match was parsed from, stored with ingest errors. `None` for
+    /// sources that convert from another shape.
+    #[serde(skip)]
+    pub raw: Option<serde_json::Value>,
+}
+
+impl ApiPlayerMatch {
+    pub fn from_raw(value: serde_json::Value) -> Result<Self, serde_json::Error> {
+        let mut api_match: Self = serde_json::from_value(value.clone())?;
+        api_match.raw = Some(value);
+        Ok(api_match)
+    }
+
+    /// The original response object if the source kept it, otherwise the typed fields.
+    pub fn raw_json(&self) -> Result<String, serde_json::Error> {
+        match &self.raw {
+            Some(raw) => serde_json::to_string(raw),
+            None => serde_json::to_string(self),
+        }
+    }
+}
+
+/// Reads a match through `ApiPlayerMatch::from_raw`, so whole responses keep each raw object.
+pub(crate) struct RawPlayerMatch(pub ApiPlayerMatch);
+
+impl<'de> Deserialize<'de> for RawPlayerMatch {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let value = serde_json::Value::deserialize(deserializer)?;
+        ApiPlayerMatch::from_raw(value).map(Self).map_err(D::Error::custom)
+    }
+}
+
+/// Fields requested via `project` so OpenDota only returns what `ApiPlayerMatch` maps.
+const PLAYER_MATCH_FIELDS: [&str; 17] = [
+    "match_id",
+    "player_slot",
+    "radiant_win",
+    "duration",
+    "game_mode",
+    "lobby_type",
+    "hero_id",
+    "start_time",
+    "version",
+    "kills",
+    "deaths",
+    "assists",
+    "average_rank",
+    "skill",
+    "leaver_status",
+    "party_size",
+    "hero_variant",
+];
+
+/// Optional filters for `/players/{id}/matches`. The default fetches the full history.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct PlayerMatchesQuery {
+    /// Only matches from the last `days` days.
+    pub days: Option<i64>,
+    pub limit: Option<u32>,
 }
 
 #[tracing::instrument(level = "trace")]
 pub(crate) async fn get_player_matches(
     player_id: i64,
-) -> Result<Vec<ApiPlayerMatch>, reqwest::Error> {
-    let url = format!("{BASE_URL}/players/{player_id}/matches");
-    info!(player_id, url, "Fetching API player matches");
-    let response = reqwest::Client::new()
-        .get(url)
-        .send()
-        .await?
-        .error_for_status()?;
-
-    let matches = response.json::<Vec<ApiPlayerMatch>>().await?;
+    filter: PlayerMatchesQuery,
+) -> Result<Vec<ApiPlayerMatch>, OpenDotaError> {
+    info!(player_id, days = filter.days, limit = filter.limit, "Fetching API player matches");
+
+    let mut query: Vec<(&str, String)> = PLAYER_MATCH_FIELDS
+        .iter()
+        .map(|field| ("project", field.to_string()))
+        .collect();
+    if let Some(days) = filter.days {
+        query.push(("date", days.to_string()));
+    }
+    if let Some(limit) = filter.limit {
+        query.push(("limit", limit.to_string()));
+    }
+
+    let raw_matches: Vec<RawPlayerMatch> =
+        open_dota_client::get_json(&format!("/players/{player_id}/matches"), &query).await?;
+    let matches: Vec<ApiPlayerMatch> = raw_matches.into_iter().map(|m| m.0).collect();
     info!(
         player_id,
         Count = matches.len(),
@@ -49,27 +123,355 @@ pub(crate) async fn get_player_matches(
 }
 
 #[allow(dead_code)]
-#[derive(Debug, Clone, Deserialize)]
+#[derive(Debug, Clone, Deserialize, Serialize)]
 pub struct ApiHeroStat {
     pub id: i32,
     pub localized_name: String,
     pub pub_pick: i64,
     pub pub_win: i64,
     pub pub_pick_trend: Vec<i64>,
+    // Per medal, Herald (1) to Immortal (8). Defaulted for sources that omit them. Old cache
+    // entries without them are avoided by the versioned cache key, not by these defaults.
+    #[serde(rename = "1_pick", default)]
+    pub bracket_1_pick: i64,
+    #[serde(rename = "1_win", default)]
+    pub bracket_1_win: i64,
+    #[serde(rename = "2_pick", default)]
+    pub bracket_2_pick: i64,
+    #[serde(rename = "2_win", default)]
+    pub bracket_2_win: i64,
+    #[serde(rename = "3_pick", default)]
+    pub bracket_3_pick: i64,
+    #[serde(rename = "3_win", default)]
+    pub bracket_3_win: i64,
+    #[serde(rename = "4_pick", default)]
+    pub bracket_4_pick: i64,
+    #[serde(rename = "4_win", default)]
+    pub bracket_4_win: i64,
+    #[serde(rename = "5_pick", default)]
+    pub bracket_5_pick: i64,
+    #[serde(rename = "5_win", default)]
+    pub bracket_5_win: i64,
+    #[serde(rename = "6_pick", default)]
+    pub bracket_6_pick: i64,
+    #[serde(rename = "6_win", default)]
+    pub bracket_6_win: i64,
+    #[serde(rename = "7_pick", default)]
+    pub bracket_7_pick: i64,
+    #[serde(rename = "7_win", default)]
+    pub bracket_7_win: i64,
+    #[serde(rename = "8_pick", default)]
+    pub bracket_8_pick: i64,
+    #[serde(rename = "8_win", default)]
+    pub bracket_8_win: i64,
+    #[serde(default)]
+    pub turbo_picks: i64,
+    #[serde(default)]
+    pub turbo_wins: i64,
+}
+
+/// Which slice of `/heroStats` to read.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum HeroStatBracket {
+    /// All public matches.
+    All,
+    /// A medal from 1 (Herald) to 8 (Immortal).
+    Medal(i32),
+    Turbo,
+}
+
+impl ApiHeroStat {
+    /// Picks and wins for `bracket`.
+    pub fn picks_and_wins(&self, bracket: HeroStatBracket) -> (i64, i64) {
+        match bracket {
+            HeroStatBracket::All => (self.pub_pick, self.pub_win),
+            HeroStatBracket::Turbo => (self.turbo_picks, self.turbo_wins),
+            HeroStatBracket::Medal(1) => (self.bracket_1_pick, self.bracket_1_win),
+            HeroStatBracket::Medal(2) => (self.bracket_2_pick, self.bracket_2_win),
+            HeroStatBracket::Medal(3) => (self.bracket_3_pick, self.bracket_3_win),
+            HeroStatBracket::Medal(4) => (self.bracket_4_pick, self.bracket_4_win),
+            HeroStatBracket::Medal(5) => (self.bracket_5_pick, self.bracket_5_win),
+            HeroStatBracket::Medal(6) => (self.bracket_6_pick, self.bracket_6_win),
+            HeroStatBracket::Medal(7) => (self.bracket_7_pick, self.bracket_7_win),
+            HeroStatBracket::Medal(8) => (self.bracket_8_pick, self.bracket_8_win),
+            HeroStatBracket::Medal(_) => (0, 0),
+        }
+    }
 }
 
 #[tracing::instrument(level = "trace")]
-pub(crate) async fn get_hero_stats() -> Result<Vec<ApiHeroStat>, reqwest::Error> {
-    let url = format!("{BASE_URL}/heroStats");
-    info!(url, "Fetching API hero stats");
-    let response = reqwest::Client::new()
-        .get(url)
-        .send()
-        .await?
-        .error_for_status()?;
-
-    let stats = response.json::<Vec<ApiHeroStat>>().await?;
+pub(crate) async fn get_hero_stats() -> Result<Vec<ApiHeroStat>, OpenDotaError> {
+    info!("Fetching API hero stats");
+    let stats: Vec<ApiHeroStat> = open_dota_client::get_json("/heroStats", &[]).await?;
     info!(count = stats.len(), "Fetched hero stats from OpenDota");
 
     Ok(stats)
 }
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct ApiHero {
+    pub id: i32,
+    /// Internal name, e.g. `npc_dota_hero_antimage`. Keys `/constants/hero_abilities`.
+    #[serde(default)]
+    pub name: String,
+    pub localized_name: String,
+    #[serde(default)]
+    pub roles: Vec<String>,
+}
+
+#[tracing::instrument(level = "trace")]
+pub(crate) async fn get_heroes() -> Result<Vec<ApiHero>, OpenDotaError> {
+    info!("Fetching API heroes");
+    let heroes: Vec<ApiHero> = open_dota_client::get_json("/heroes", &[]).await?;
+    info!(count = heroes.len(), "Fetched heroes from OpenDota");
+
+    Ok(heroes)
+}
+
+/// How a hero fares against one opponent. `wins` are the queried hero's wins.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct ApiHeroMatchup {
+    pub hero_id: i32,
+    pub games_played: i64,
+    pub wins: i64,
+}
+
+#[tracing::instrument(level = "trace")]
+pub(crate) async fn get_hero_matchups(hero_id: i32) -> Result<Vec<ApiHeroMatchup>, OpenDotaError> {
+    info!(hero_id, "Fetching API hero matchups");
+    let matchups: Vec<ApiHeroMatchup> =
+        open_dota_client::get_json(&format!("/heroes/{hero_id}/matchups"), &[]).await?;
+    info!(hero_id, count = matchups.len(), "Fetched hero matchups from OpenDota");
+
+    Ok(matchups)
+}
+
+/// Percentile curves per stat (e.g. `gold_per_min`, `kills_per_min`) for a hero.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct ApiBenchmarks {
+    pub hero_id: i32,
+    #[serde(default)]
+    pub result: HashMap<String, Vec<ApiBenchmarkPoint>>,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct ApiBenchmarkPoint {
+    /// 0.0 to 1.0.
+    pub percentile: f64,
+    pub value: f64,
+}
+
+#[tracing::instrument(level = "trace")]
+pub(crate) async fn get_benchmarks(hero_id: i32) -> Result<ApiBenchmarks, OpenDotaError> {
+    info!(hero_id, "Fetching API hero benchmarks");
+    let benchmarks: ApiBenchmarks =
+        open_dota_client::get_json("/benchmarks", &[("hero_id", hero_id.to_string())]).await?;
+    info!(hero_id, stats = benchmarks.result.len(), "Fetched hero benchmarks from OpenDota");
+
+    Ok(benchmarks)
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct ApiHeroAbilities {
+    /// In `hero_variant` order, so variant 1 is the first facet.
+    #[serde(default)]
+    pub facets: Vec<ApiHeroFacet>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct ApiHeroFacet {
+    pub name: String,
+    pub title: Option<String>,
+}
+
+/// Keyed by the hero's internal name.
+#[tracing::instrument(level = "trace")]
+pub(crate) async fn get_hero_abilities() -> Result<HashMap<String, ApiHeroAbilities>, OpenDotaError> {
+    info!("Fetching API hero abilities");
+    let abilities: HashMap<String, ApiHeroAbilities> =
+        open_dota_client::get_json("/constants/hero_abilities", &[]).await?;
+    info!(count = abilities.len(), "Fetched hero abilities from OpenDota");
+
+    Ok(abilities)
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct ApiPatch {
+    pub id: i32,
+    pub name: String,
+    /// RFC 3339 release date.
+    pub date: String,
+}
+
+#[tracing::instrument(level = "trace")]
+pub(crate) async fn get_patches() -> Result<Vec<ApiPatch>, OpenDotaError> {
+    info!("Fetching API patches");
+    let patches: Vec<ApiPatch> = open_dota_client::get_json("/constants/patch", &[]).await?;
+    info!(count = patches.len(), "Fetched patches from OpenDota");
+
+    Ok(patches)
+}
+
+#[allow(dead_code)]
+#[derive(Debug, Clone, Deserialize)]
+pub struct ApiMatchDetails {
+    pub match_id: i64,
+    pub start_time: Option<i64>,
+    /// Only set once OpenDota has parsed the replay.
+    pub version: Option<i32>,
+    #[serde(default)]
+    pub players: Vec<ApiMatchPlayer>,
+}
+
+impl ApiMatchDetails {
+    pub fn is_parsed(&self) -> bool {
+        self.version.is_some()
+    }
+}
+
+#[allow(dead_code)]
+#[derive(Debug, Clone, Deserialize)]
+pub struct ApiMatchPlayer {
+    /// `None` when the player hides their match data.
+    pub account_id: Option<i64>,
+    pub player_slot: Option<i32>,
+    pub hero_id: Option<i32>,
+    pub gold_per_min: Option<i32>,
+    pub xp_per_min: Option<i32>,
+    pub last_hits: Option<i32>,
+    pub denies: Option<i32>,
+    pub hero_damage: Option<i32>,
+    pub tower_damage: Option<i32>,
+    pub hero_healing: Option<i32>,
+    pub net_worth: Option<i32>,
+    pub item_0: Option<i32>,
+    pub item_1: Option<i32>,
+    pub item_2: Option<i32>,
+    pub item_3: Option<i32>,
+    pub item_4: Option<i32>,
+    pub item_5: Option<i32>,
+    pub item_neutral: Option<i32>,
+}
+
+#[tracing::instrument(level = "trace")]
+pub(crate) async fn get_match_details(match_id: i64) -> Result<ApiMatchDetails, OpenDotaError> {
+    info!(match_id, "Fetching API match details");
+    let details: ApiMatchDetails =
+        open_dota_client::get_json(&format!("/matches/{match_id}"), &[]).await?;
+    info!(
+        match_id,
+        players = details.players.len(),
+        "Fetched match details from OpenDota"
+    );
+
+    Ok(details)
+}
+
+#[derive(Debug, Clone, Deserialize)]
+struct ApiParseRequest {
+    job: Option<ApiParseJob>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+struct ApiParseJob {
+    #[serde(rename = "jobId")]
+    job_id: i64,
+}
+
+/// Asks OpenDota to parse the match replay. Returns the job id to poll, if one was created.
+#[tracing::instrument(level = "trace")]
+pub(crate) async fn request_parse(match_id: i64) -> Result<Option<i64>, OpenDotaError> {
+    info!(match_id, "Requesting match parse");
+    let request: ApiParseRequest =
+        open_dota_client::post_json(&format!("/request/{match_id}"), &[]).await?;
+
+    Ok(request.job.map(|job| job.job_id))
+}
+
+/// Returns `true` while the job is still queued or running. OpenDota returns `null` once it has finished.
+#[tracing::instrument(level = "trace")]
+pub(crate) async fn is_parse_job_pending(job_id: i64) -> Result<bool, OpenDotaError> {
+    let job: Option<serde_json::Value> =
+        open_dota_client::get_json(&format!("/request/{job_id}"), &[]).await?;
+
+    Ok(job.is_some())
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct ApiPlayer {
+    /// Missing when OpenDota has never seen the account.
+    pub profile: Option<ApiPlayerProfile>,
+    pub rank_tier: Option<i32>,
+    pub leaderboard_rank: Option<i32>,
+}
+
+#[allow(dead_code)]
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct ApiPlayerProfile {
+    pub account_id: i64,
+    pub personaname: Option<String>,
+    pub avatarfull: Option<String>,
+    pub last_login: Option<String>,
+}
+
+#[tracing::instrument(level = "trace")]
+pub(crate) async fn get_player(player_id: i64) -> Result<ApiPlayer, OpenDotaError> {
+    info!(player_id, "Fetching API player profile");
+    let player: ApiPlayer = open_dota_client::get_json(&format!("/players/{player_id}"), &[]).await?;
+
+    Ok(player)
+}
+
+/// The live OpenDota API.
+pub struct OpenDotaSource;
+
+#[async_trait]
+impl MatchSource for OpenDotaSource {
+    fn name(&self) -> &'static str {
+        "open_dota"
+    }
+
+    async fn player_matches(
+        &self,
+        player_id: i64,
+        query: PlayerMatchesQuery,
+    ) -> Result<Vec<ApiPlayerMatch>, Error> {
+        Ok(get_player_matches(player_id, query).await?)
+    }
+
+    async fn hero_stats(&self) -> Result<Vec<ApiHeroStat>, Error> {
+        Ok(get_hero_stats().await?)
+    }
+
+    async fn heroes(&self) -> Result<Vec<ApiHero>, Error> {
+        Ok(get_heroes().await?)
+    }
+
+    async fn hero_matchups(&self, hero_id: i32) -> Result<Vec<ApiHeroMatchup>, Error> {
+        Ok(get_hero_matchups(hero_id).await?)
+    }
+
+    async fn hero_benchmarks(&self, hero_id: i32) -> Result<ApiBenchmarks, Error> {
+        Ok(get_benchmarks(hero_id).await?)
+    }
+
+    async fn hero_abilities(&self) -> Result<HashMap<String, ApiHeroAbilities>, Error> {
+        Ok(get_hero_abilities().await?)
+    }
+
+    async fn patches(&self) -> Result<Vec<ApiPatch>, Error> {
+        Ok(get_patches().await?)
+    }
+
+    async fn player(&self, player_id: i64) -> Result<ApiPlayer, Error> {
+        Ok(get_player(player_id).await?)
+    }
+
+    async fn match_details(&self, match_id: i64) -> Result<Option<ApiMatchDetails>, Error> {
+        match get_match_details(match_id).await {
+            Ok(details) => Ok(Some(details)),
+            Err(OpenDotaError::Status { status, .. }) if status == StatusCode::NOT_FOUND => Ok(None),
+            Err(e) => Err(e.into()),
+        }
+    }
+}
@@ -0,0 +1,423 @@
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::OnceLock;
+use std::time::{Duration, Instant};
+
+use reqwest::header::RETRY_AFTER;
+use reqwest::{Method, Response, StatusCode};
+use serde::de::DeserializeOwned;
+use tokio::sync::Mutex;
+use tracing::{debug, info, warn};
+
+use super::circuit_breaker::{Admission, CircuitBreaker};
+use crate::config::{CircuitBreakerConfig, OpenDotaConfig};
+use crate::Error;
+
+const DEFAULT_BASE_URL: &str = "https://api.opendota.com/api";
+
+const REQUEST_TIMEOUT_SECS: u64 = 30;
+const CONNECT_TIMEOUT_SECS: u64 = 10;
+const MAX_RETRIES: u32 = 3;
+const BASE_BACKOFF_MS: u64 = 1000;
+const MAX_BACKOFF_SECS: u64 = 60;
+
+const DEFAULT_CIRCUIT_BREAKER: CircuitBreakerConfig = CircuitBreakerConfig {
+    failure_threshold: 5,
+    probe_interval_secs: 120,
+};
+/// Cheap endpoint used to check whether OpenDota is back while the circuit is open.
+const PROBE_ENDPOINT: &str = "/heroes";
+
+/// OpenDota free tier allows 60 calls per minute.
+pub const FREE_TIER_PER_MINUTE: u32 = 60;
+/// Max requests allowed to go out back-to-back before the limiter starts spacing them.
+pub(crate) const BURST_SIZE: u32 = 5;
+
+#[derive(Debug)]
+pub enum OpenDotaError {
+    Request(reqwest::Error),
+    Status { status: StatusCode, endpoint: String },
+    /// The circuit breaker is open, so the request never went out.
+    Unavailable { since: i64 },
+}
+
+impl OpenDotaError {
+    /// Whether the error points at OpenDota being down rather than a bad request.
+    fn is_outage(&self) -> bool {
+        match self {
+            Self::Request(_) => true,
+            Self::Status { status, .. } => status.is_server_error(),
+            Self::Unavailable { .. } => false,
+        }
+    }
+}
+
+impl std::fmt::Display for OpenDotaError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Request(e) => write!(f, "OpenDota request failed: {e}"),
+            Self::Status { status, endpoint } => {
+                write!(f, "OpenDota returned {status} for {endpoint}")
+            }
+            Self::Unavailable { since } => {
+                write!(f, "OpenDota has been unavailable since {since}, skipping request")
+            }
+        }
+    }
+}
+
+impl std::error::Error for OpenDotaError {}
+
+impl From<reqwest::Error> for OpenDotaError {
+    /// Strips the URL so the `api_key` query parameter never ends up in error messages.
+    fn from(e: reqwest::Error) -> Self {
+        Self::Request(e.without_url())
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct MetricsSnapshot {
+    pub requests: u64,
+    pub retries: u64,
+    pub rate_limited: u64,
+    pub failures: u64,
+}
+
+#[derive(Default)]
+struct Metrics {
+    requests: AtomicU64,
+    retries: AtomicU64,
+    rate_limited: AtomicU64,
+    failures: AtomicU64,
+}
+
+struct BucketState {
+    tokens: f64,
+    last_refill: Instant,
+    paused_until: Option<Instant>,
+}
+
+/// Token bucket shared by every OpenDota request so concurrent reloads stay under the tier limit.
+pub(crate) struct TokenBucket {
+    capacity: f64,
+    refill_per_sec: f64,
+    state: Mutex<BucketState>,
+}
+
+impl TokenBucket {
+    pub(crate) fn new(per_minute: u32, burst: u32) -> Self {
+        let capacity = burst.max(1) as f64;
+        Self {
+            capacity,
+            refill_per_sec: per_minute.max(1) as f64 / 60.0,
+            state: Mutex::new(BucketState {
+                tokens: capacity,
+                last_refill: Instant::now(),
+                paused_until: None,
+            }),
+        }
+    }
+
+    pub(crate) async fn acquire(&self) {
+        loop {
+            let wait = {
+                let mut state = self.state.lock().await;
+                let now = Instant::now();
+
+                match state.paused_until {
+                    Some(until) if until > now => until - now,
+                    _ => {
+                        state.paused_until = None;
+                        let elapsed = now.duration_since(state.last_refill).as_secs_f64();
+                        state.tokens = (state.tokens + elapsed * self.refill_per_sec).min(self.capacity);
+                        state.last_refill = now;
+
+                        if state.tokens >= 1.0 {
+                            state.tokens -= 1.0;
+                            return;
+                        }
+
+                        Duration::from_secs_f64((1.0 - state.tokens) / self.refill_per_sec)
+                    }
+                }
+            };
+
+            tokio::time::sleep(wait).await;
+        }
+    }
+
+    /// Stops handing out tokens until `delay` has passed, e.g. after OpenDota sends `Retry-After`.
+    async fn pause(&self, delay: Duration) {
+        let mut state = self.state.lock().await;
+        let until = Instant::now() + delay;
+        match state.paused_until {
+            Some(current) if current >= until => {}
+            _ => state.paused_until = Some(until),
+        }
+        state.tokens = 0.0;
+    }
+}
+
+struct OpenDotaClient {
+    http: reqwest::Client,
+    base_url: String,
+    /// Appended to every request as `api_key`. Never logged.
+    api_key: Option<String>,
+    limiter: TokenBucket,
+    breaker: CircuitBreaker,
+    metrics: Metrics,
+}
+
+static CLIENT: OnceLock<OpenDotaClient> = OnceLock::new();
+
+/// Falls back to an anonymous free-tier client when `init_client` was never called (e.g. CLI subcommands).
+fn client() -> &'static OpenDotaClient {
+    CLIENT.get_or_init(|| {
+        OpenDotaClient::new(
+            DEFAULT_BASE_URL.to_string(),
+            None,
+            FREE_TIER_PER_MINUTE,
+            &DEFAULT_CIRCUIT_BREAKER,
+        )
+    })
+}
+
+pub fn init_client(config: &OpenDotaConfig) -> Result<(), Error> {
+    let api_key = match &config.api_key_var {
+        Some(var) => Some(
+            std::env::var(var).map_err(|e| format!("Failed to read env var '{}': {}", var, e))?,
+        ),
+        None => None,
+    };
+
+    let requests_per_minute = match api_key {
+        Some(_) => config.premium_requests_per_minute,
+        None => config.requests_per_minute,
+    };
+
+    // A local stand-in can replace the real API for offline staging runs.
+    let base_url = config
+        .base_url
+        .as_deref()
+        .unwrap_or(DEFAULT_BASE_URL)
+        .trim_end_matches('/')
+        .to_string();
+
+    CLIENT
+        .set(OpenDotaClient::new(
+            base_url,
+            api_key,
+            requests_per_minute,
+            &config.circuit_breaker,
+        ))
+        .map_err(|_already| {
+            Box::new(std::io::Error::new(
+                std::io::ErrorKind::AlreadyExists,
+                "OpenDota client already initialized",
+            )) as Error
+        })?;
+
+    Ok(())
+}
+
+impl OpenDotaClient {
+    fn new(
+        base_url: String,
+        api_key: Option<String>,
+        requests_per_minute: u32,
+        breaker: &CircuitBreakerConfig,
+    ) -> Self {
+        let http = reqwest::Client::builder()
+            .timeout(Duration::from_secs(REQUEST_TIMEOUT_SECS))
+            .connect_timeout(Duration::from_secs(CONNECT_TIMEOUT_SECS))
+            .user_agent("dotacord")
+            .build()
+            .expect("Failed to build OpenDota HTTP client");
+
+        info!(
+            base_url = base_url.as_str(),
+            requests_per_minute,
+            premium = api_key.is_some(),
+            "OpenDota client initialized"
+        );
+
+        Self {
+            http,
+            base_url,
+            api_key,
+            limiter: TokenBucket::new(requests_per_minute, BURST_SIZE),
+            breaker: CircuitBreaker::new("opendota", breaker),
+            metrics: Metrics::default(),
+        }
+    }
+
+    async fn send(
+        &self,
+        method: Method,
+        endpoint: &str,
+        query: &[(&str, String)],
+    ) -> Result<Response, OpenDotaError> {
+        if let Admission::Rejected { since } = self.breaker.admit() {
+            debug!(endpoint, since, "OpenDota circuit open, rejecting request");
+            return Err(OpenDotaError::Unavailable { since });
+        }
+
+        let result = self.send_with_retries(method, endpoint, query).await;
+        match &result {
+            Err(e) if e.is_outage() => self.breaker.record_failure(),
+            _ => self.breaker.record_success(),
+        }
+        result
+    }
+
+    async fn send_with_retries(
+        &self,
+        method: Method,
+        endpoint: &str,
+        query: &[(&str, String)],
+    ) -> Result<Response, OpenDotaError> {
+        let url = format!("{}{endpoint}", self.base_url);
+        let mut query = query.to_vec();
+        if let Some(key) = &self.api_key {
+            query.push(("api_key", key.clone()));
+        }
+        let mut attempt = 0;
+
+        loop {
+            self.limiter.acquire().await;
+            self.metrics.requests.fetch_add(1, Ordering::Relaxed);
+
+            let started = Instant::now();
+            let result = self
+                .http
+                .request(method.clone(), &url)
+                .query(&query)
+                .send()
+                .await;
+            let elapsed_ms = started.elapsed().as_millis() as u64;
+
+            let retry_delay = match result {
+                Ok(response) if response.status().is_success() => {
+                    debug!(
+                        endpoint,
+                        status = response.status().as_u16(),
+                        attempt,
+                        elapsed_ms,
+                        "OpenDota request completed"
+                    );
+                    return Ok(response);
+                }
+                Ok(response) => {
+                    let status = response.status();
+                    let retry_after = parse_retry_after(&response);
+                    warn!(
+                        endpoint,
+                        status = status.as_u16(),
+                        attempt,
+                        elapsed_ms,
+                        retry_after_secs = retry_after.map(|d| d.as_secs()),
+                        "OpenDota request failed"
+                    );
+
+                    if status == StatusCode::TOO_MANY_REQUESTS {
+                        self.metrics.rate_limited.fetch_add(1, Ordering::Relaxed);
+                        self.limiter
+                            .pause(retry_after.unwrap_or_else(|| backoff_delay(attempt)))
+                            .await;
+                    }
+
+                    if !is_retryable_status(status) || attempt >= MAX_RETRIES {
+                        self.metrics.failures.fetch_add(1, Ordering::Relaxed);
+                        return Err(OpenDotaError::Status {
+                            status,
+                            endpoint: endpoint.to_string(),
+                        });
+                    }
+
+                    retry_after.unwrap_or_else(|| backoff_delay(attempt))
+                }
+                Err(e) => {
+                    let e = e.without_url();
+                    warn!(endpoint, attempt, elapsed_ms, error = %e, "OpenDota request error");
+
+                    if !(e.is_timeout() || e.is_connect()) || attempt >= MAX_RETRIES {
+                        self.metrics.failures.fetch_add(1, Ordering::Relaxed);
+                        return Err(e.into());
+                    }
+
+                    backoff_delay(attempt)
+                }
+            };
+
+            attempt += 1;
+            self.metrics.retries.fetch_add(1, Ordering::Relaxed);
+            tokio::time::sleep(retry_delay).await;
+        }
+    }
+}
+
+fn is_retryable_status(status: StatusCode) -> bool {
+    status == StatusCode::TOO_MANY_REQUESTS || status.is_server_error()
+}
+
+fn backoff_delay(attempt: u32) -> Duration {
+    let delay_ms = BASE_BACKOFF_MS.saturating_mul(1 << attempt.min(6));
+    Duration::from_millis(delay_ms).min(Duration::from_secs(MAX_BACKOFF_SECS))
+}
+
+fn parse_retry_after(response: &Response) -> Option<Duration> {
+    response
+        .headers()
+        .get(RETRY_AFTER)
+        .and_then(|v| v.to_str().ok())
+        .and_then(|v| v.trim().parse::<u64>().ok())
+        .map(|secs| Duration::from_secs(secs.min(MAX_BACKOFF_SECS)))
+}
+
+/// Sends a rate-limited, retrying GET to `endpoint` (e.g. `/heroStats`) and deserializes the body.
+pub(crate) async fn get_json<T: DeserializeOwned>(
+    endpoint: &str,
+    query: &[(&str, String)],
+) -> Result<T, OpenDotaError> {
+    let response = client().send(Method::GET, endpoint, query).await?;
+    Ok(response.json::<T>().await?)
+}
+
+/// Sends a rate-limited, retrying POST to `endpoint` (e.g. `/request/{match_id}`) and deserializes the body.
+pub(crate) async fn post_json<T: DeserializeOwned>(
+    endpoint: &str,
+    query: &[(&str, String)],
+) -> Result<T, OpenDotaError> {
+    let response = client().send(Method::POST, endpoint, query).await?;
+    Ok(response.json::<T>().await?)
+}
+
+/// While the circuit is open, sends a probe if one is due. Returns whether OpenDota is usable.
+pub async fn check_available() -> bool {
+    let breaker = &client().breaker;
+    if breaker.open_since().is_none() {
+        return true;
+    }
+    if !breaker.is_available() {
+        return false;
+    }
+
+    if let Err(e) = client().send(Method::GET, PROBE_ENDPOINT, &[]).await {
+        info!(error = %e, "OpenDota probe failed");
+    }
+    breaker.open_since().is_none()
+}
+
+/// Unix time OpenDota went down, while the circuit is open.
+pub fn outage_since() -> Option<i64> {
+    client().breaker.open_since()
+}
+
+pub fn metrics() -> MetricsSnapshot {
+    let metrics = &client().metrics;
+    MetricsSnapshot {
+        requests: metrics.requests.load(Ordering::Relaxed),
+        retries: metrics.retries.load(Ordering::Relaxed),
+        rate_limited: metrics.rate_limited.load(Ordering::Relaxed),
+        failures: metrics.failures.load(Ordering::Relaxed),
+    }
+}
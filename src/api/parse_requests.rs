@@ -0,0 +1,182 @@
+use std::collections::HashMap;
+
+use chrono::Utc;
+use tracing::{info, warn};
+
+use crate::api::{match_source, open_dota_api};
+use crate::config::ParseJobsConfig;
+use crate::database::parse_jobs_db::{self, ParseJobModel, ParseJobState};
+use crate::database::{match_details_db, player_matches_db};
+use crate::Error;
+
+const SECONDS_PER_DAY: i64 = 86_400;
+
+/// Runs one pass of the parse pipeline: queue recent unparsed matches, submit queued jobs to
+/// OpenDota, then poll submitted jobs and re-fetch details for the ones that finished.
+#[tracing::instrument(level = "trace", skip(config))]
+pub async fn process_parse_jobs(config: &ParseJobsConfig) -> Result<(), Error> {
+    let now = Utc::now().timestamp();
+
+    let queued = queue_unparsed_matches(config, now).await?;
+    let submitted = submit_queued_jobs(config, now).await?;
+    let (parsed, failed) = poll_requested_jobs(config, now).await?;
+
+    if queued + submitted + parsed + failed > 0 {
+        info!(queued, submitted, parsed, failed, "Processed parse jobs");
+    }
+
+    Ok(())
+}
+
+/// Queues recent matches without parsed details, and re-queues failed jobs once
+/// `retry_failed_after_hours` has passed, since OpenDota may be able to parse the replay by then.
+async fn queue_unparsed_matches(config: &ParseJobsConfig, now: i64) -> Result<usize, Error> {
+    let since = now - config.max_match_age_days * SECONDS_PER_DAY;
+    let unparsed = match_details_db::query_unparsed_match_ids(since).await?;
+    if unparsed.is_empty() {
+        return Ok(0);
+    }
+
+    let existing: HashMap<i64, ParseJobModel> =
+        parse_jobs_db::query_jobs_by_match_ids(unparsed.clone())
+            .await?
+            .into_iter()
+            .map(|job| (job.match_id, job))
+            .collect();
+
+    let retry_before = now - config.retry_failed_after_hours * 3600;
+    let mut queued = 0;
+    for match_id in unparsed {
+        match existing.get(&match_id) {
+            None => parse_jobs_db::insert_queued_job(match_id, now).await?,
+            Some(job)
+                if job.state == ParseJobState::Failed.as_str() && job.updated_at < retry_before =>
+            {
+                parse_jobs_db::update_job(
+                    match_id,
+                    ParseJobState::Queued,
+                    None,
+                    0,
+                    job.last_error.clone(),
+                    now,
+                )
+                .await?;
+            }
+            Some(_) => continue,
+        }
+        queued += 1;
+    }
+
+    Ok(queued)
+}
+
+async fn submit_queued_jobs(config: &ParseJobsConfig, now: i64) -> Result<usize, Error> {
+    let jobs = parse_jobs_db::query_jobs_by_state(ParseJobState::Queued, config.batch_size).await?;
+
+    let mut submitted = 0;
+    for job in jobs {
+        let attempts = job.attempts + 1;
+        match open_dota_api::request_parse(job.match_id).await {
+            Ok(job_id) => {
+                parse_jobs_db::update_job(
+                    job.match_id,
+                    ParseJobState::Requested,
+                    job_id,
+                    attempts,
+                    None,
+                    now,
+                )
+                .await?;
+                submitted += 1;
+            }
+            Err(e) => {
+                warn!(match_id = job.match_id, attempts, error = %e, "Parse request failed");
+                let state = if attempts >= config.max_attempts {
+                    ParseJobState::Failed
+                } else {
+                    ParseJobState::Queued
+                };
+                parse_jobs_db::update_job(job.match_id, state, None, attempts, Some(e.to_string()), now)
+                    .await?;
+            }
+        }
+    }
+
+    Ok(submitted)
+}
+
+async fn poll_requested_jobs(config: &ParseJobsConfig, now: i64) -> Result<(usize, usize), Error> {
+    let jobs =
+        parse_jobs_db::query_jobs_by_state(ParseJobState::Requested, config.batch_size).await?;
+
+    let (mut parsed, mut failed) = (0, 0);
+    for job in jobs {
+        if let Some(job_id) = job.job_id {
+            match open_dota_api::is_parse_job_pending(job_id).await {
+                Ok(true) => {
+                    if now - job.updated_at > config.timeout_mins * 60 {
+                        fail_job(&job, "Timed out waiting for OpenDota to parse", now).await?;
+                        failed += 1;
+                    }
+                    continue;
+                }
+                Ok(false) => {}
+                Err(e) => {
+                    warn!(match_id = job.match_id, job_id, error = %e, "Failed to poll parse job");
+                    continue;
+                }
+            }
+        }
+
+        if refetch_parsed_details(job.match_id, now).await? {
+            parse_jobs_db::update_job(
+                job.match_id,
+                ParseJobState::Parsed,
+                job.job_id,
+                job.attempts,
+                None,
+                now,
+            )
+            .await?;
+            parsed += 1;
+        } else {
+            fail_job(&job, "Job finished but the replay is still unparsed", now).await?;
+            failed += 1;
+        }
+    }
+
+    Ok((parsed, failed))
+}
+
+/// Re-fetches the match and replaces its detail rows. Returns `false` if the match is still unparsed.
+async fn refetch_parsed_details(match_id: i64, now: i64) -> Result<bool, Error> {
+    let Some(api_details) = match_source::source().match_details(match_id).await? else {
+        return Ok(false);
+    };
+    if !api_details.is_parsed() {
+        return Ok(false);
+    }
+
+    let players: Vec<(i64, i32)> = player_matches_db::query_match_by_id(match_id)
+        .await?
+        .into_iter()
+        .map(|m| (m.player_id, m.hero_id))
+        .collect();
+    let details = match_details_db::map_match_details(Some(&api_details), match_id, &players, now);
+    match_details_db::replace_details(match_id, details).await?;
+
+    Ok(true)
+}
+
+async fn fail_job(job: &ParseJobModel, reason: &str, now: i64) -> Result<(), Error> {
+    warn!(match_id = job.match_id, reason, "Parse job failed");
+    parse_jobs_db::update_job(
+        job.match_id,
+        ParseJobState::Failed,
+        job.job_id,
+        job.attempts,
+        Some(reason.to_string()),
+        now,
+    )
+    .await
+}
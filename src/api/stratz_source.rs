@@ -0,0 +1,513 @@
+use std::collections::HashMap;
+use std::time::Duration;
+
+use chrono::Utc;
+use poise::serenity_prelude::async_trait;
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+use tracing::{debug, info, warn};
+
+use super::match_source::MatchSource;
+use super::open_dota_api::{
+    ApiBenchmarks, ApiHero, ApiHeroAbilities, ApiHeroMatchup, ApiHeroStat, ApiMatchDetails,
+    ApiPatch, ApiPlayer, ApiPlayerMatch, OpenDotaSource, PlayerMatchesQuery,
+};
+use super::open_dota_client::{TokenBucket, BURST_SIZE};
+use crate::config::StratzConfig;
+use crate::Error;
+
+const DEFAULT_BASE_URL: &str = "https://api.stratz.com/graphql";
+const REQUEST_TIMEOUT_SECS: u64 = 30;
+const SECONDS_PER_DAY: i64 = 86_400;
+/// STRATZ caps `take` at 100 per page.
+const PAGE_SIZE: u32 = 100;
+
+const PLAYER_MATCHES_QUERY: &str = r#"
+query PlayerMatches($steamAccountId: Long!, $take: Int!, $skip: Int!, $startDateTime: Long) {
+  player(steamAccountId: $steamAccountId) {
+    matches(request: { take: $take, skip: $skip, startDateTime: $startDateTime }) {
+      id
+      didRadiantWin
+      durationSeconds
+      startDateTime
+      gameMode
+      lobbyType
+      rank
+      players {
+        steamAccountId
+        isRadiant
+        playerSlot
+        heroId
+        kills
+        deaths
+        assists
+        leaverStatus
+        partyId
+        variant
+      }
+    }
+  }
+}
+"#;
+
+#[derive(Serialize)]
+struct GraphQlRequest<'a> {
+    query: &'a str,
+    variables: serde_json::Value,
+}
+
+#[derive(Deserialize)]
+struct GraphQlResponse<T> {
+    data: Option<T>,
+    #[serde(default)]
+    errors: Vec<GraphQlError>,
+}
+
+#[derive(Deserialize)]
+struct GraphQlError {
+    message: String,
+}
+
+#[derive(Deserialize)]
+struct PlayerMatchesData {
+    player: Option<StratzPlayer>,
+}
+
+#[derive(Deserialize)]
+struct StratzPlayer {
+    #[serde(default)]
+    matches: Vec<StratzMatch>,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct StratzMatch {
+    id: i64,
+    did_radiant_win: Option<bool>,
+    duration_seconds: Option<i32>,
+    start_date_time: Option<i64>,
+    game_mode: Option<String>,
+    lobby_type: Option<String>,
+    rank: Option<i32>,
+    #[serde(default)]
+    players: Vec<StratzMatchPlayer>,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct StratzMatchPlayer {
+    steam_account_id: Option<i64>,
+    is_radiant: Option<bool>,
+    player_slot: Option<i32>,
+    hero_id: Option<i32>,
+    kills: Option<i32>,
+    deaths: Option<i32>,
+    assists: Option<i32>,
+    leaver_status: Option<String>,
+    party_id: Option<i64>,
+    variant: Option<i32>,
+}
+
+/// STRATZ GraphQL API for player matches. Everything else is delegated to OpenDota,
+/// since STRATZ responses don't share the OpenDota shapes for hero stats and profiles.
+pub struct StratzSource {
+    http: reqwest::Client,
+    base_url: String,
+    api_key: String,
+    limiter: TokenBucket,
+    fallback: OpenDotaSource,
+}
+
+impl StratzSource {
+    pub fn new(config: &StratzConfig) -> Result<Self, Error> {
+        let api_key = std::env::var(&config.api_key_var).map_err(|e| {
+            format!("Failed to read env var '{}': {}", &config.api_key_var, e)
+        })?;
+
+        let http = reqwest::Client::builder()
+            .timeout(Duration::from_secs(REQUEST_TIMEOUT_SECS))
+            // STRATZ rejects requests without this user agent.
+            .user_agent("STRATZ_API")
+            .build()?;
+
+        let base_url = config
+            .base_url
+            .clone()
+            .unwrap_or_else(|| DEFAULT_BASE_URL.to_string());
+        info!(
+            base_url = base_url.as_str(),
+            requests_per_minute = config.requests_per_minute,
+            "STRATZ client initialized"
+        );
+
+        Ok(Self {
+            http,
+            base_url,
+            api_key,
+            limiter: TokenBucket::new(config.requests_per_minute, BURST_SIZE),
+            fallback: OpenDotaSource,
+        })
+    }
+
+    async fn query<T: serde::de::DeserializeOwned>(
+        &self,
+        query: &str,
+        variables: serde_json::Value,
+    ) -> Result<T, Error> {
+        self.limiter.acquire().await;
+
+        let response = self
+            .http
+            .post(&self.base_url)
+            .bearer_auth(&self.api_key)
+            .json(&GraphQlRequest { query, variables })
+            .send()
+            .await
+            .map_err(|e| e.without_url())?;
+
+        let status = response.status();
+        if !status.is_success() {
+            return Err(format!("STRATZ returned {status}").into());
+        }
+
+        let body: GraphQlResponse<T> = response.json().await.map_err(|e| e.without_url())?;
+        if !body.errors.is_empty() {
+            let messages: Vec<String> = body.errors.into_iter().map(|e| e.message).collect();
+            return Err(format!("STRATZ query failed: {}", messages.join("; ")).into());
+        }
+
+        body.data.ok_or_else(|| "STRATZ response had no data".into())
+    }
+}
+
+#[async_trait]
+impl MatchSource for StratzSource {
+    fn name(&self) -> &'static str {
+        "stratz"
+    }
+
+    async fn player_matches(
+        &self,
+        player_id: i64,
+        query: PlayerMatchesQuery,
+    ) -> Result<Vec<ApiPlayerMatch>, Error> {
+        let start_date_time = query
+            .days
+            .map(|days| Utc::now().timestamp() - days * SECONDS_PER_DAY);
+        let mut matches = Vec::new();
+        let mut skip = 0;
+
+        loop {
+            let take = match query.limit {
+                Some(limit) => PAGE_SIZE.min(limit.saturating_sub(skip)),
+                None => PAGE_SIZE,
+            };
+            if take == 0 {
+                break;
+            }
+
+            let variables = json!({
+                "steamAccountId": player_id,
+                "take": take,
+                "skip": skip,
+                "startDateTime": start_date_time,
+            });
+            let data: PlayerMatchesData = self.query(PLAYER_MATCHES_QUERY, variables).await?;
+            let page = data.player.map(|p| p.matches).unwrap_or_default();
+            let page_len = page.len() as u32;
+            debug!(player_id, skip, page_len, "Fetched STRATZ match page");
+
+            matches.extend(page.into_iter().filter_map(|m| to_api_match(m, player_id)));
+
+            if page_len < take {
+                break;
+            }
+            skip += page_len;
+        }
+
+        info!(player_id, count = matches.len(), "Fetched player matches from STRATZ");
+        Ok(matches)
+    }
+
+    async fn hero_stats(&self) -> Result<Vec<ApiHeroStat>, Error> {
+        self.fallback.hero_stats().await
+    }
+
+    async fn heroes(&self) -> Result<Vec<ApiHero>, Error> {
+        self.fallback.heroes().await
+    }
+
+    async fn hero_matchups(&self, hero_id: i32) -> Result<Vec<ApiHeroMatchup>, Error> {
+        self.fallback.hero_matchups(hero_id).await
+    }
+
+    async fn hero_benchmarks(&self, hero_id: i32) -> Result<ApiBenchmarks, Error> {
+        self.fallback.hero_benchmarks(hero_id).await
+    }
+
+    async fn hero_abilities(&self) -> Result<HashMap<String, ApiHeroAbilities>, Error> {
+        self.fallback.hero_abilities().await
+    }
+
+    async fn patches(&self) -> Result<Vec<ApiPatch>, Error> {
+        self.fallback.patches().await
+    }
+
+    async fn player(&self, player_id: i64) -> Result<ApiPlayer, Error> {
+        self.fallback.player(player_id).await
+    }
+
+    async fn match_details(&self, match_id: i64) -> Result<Option<ApiMatchDetails>, Error> {
+        self.fallback.match_details(match_id).await
+    }
+}
+
+/// Converts a STRATZ match into the OpenDota shape so it goes through `map_to_player_match` unchanged.
+fn to_api_match(stratz_match: StratzMatch, player_id: i64) -> Option<ApiPlayerMatch> {
+    let Some(player) = stratz_match
+        .players
+        .iter()
+        .find(|p| p.steam_account_id == Some(player_id))
+    else {
+        warn!(match_id = stratz_match.id, player_id, "Player missing from STRATZ match");
+        return None;
+    };
+
+    let mut party_sizes: HashMap<i64, i32> = HashMap::new();
+    for party_id in stratz_match.players.iter().filter_map(|p| p.party_id) {
+        *party_sizes.entry(party_id).or_default() += 1;
+    }
+
+    // OpenDota slots are 0-4 for Radiant and 128-132 for Dire. STRATZ may send either those or 0-9.
+    let player_slot = match (player.is_radiant, player.player_slot) {
+        (Some(is_radiant), Some(slot)) => {
+            let position = if slot >= 128 { slot - 128 } else { slot % 5 };
+            Some(if is_radiant { position } else { 128 + position })
+        }
+        _ => None,
+    };
+
+    Some(ApiPlayerMatch {
+        match_id: stratz_match.id,
+        player_slot,
+        radiant_win: stratz_match.did_radiant_win,
+        duration: stratz_match.duration_seconds,
+        game_mode: stratz_match.game_mode.as_deref().and_then(game_mode_id),
+        lobby_type: stratz_match.lobby_type.as_deref().and_then(lobby_type_id),
+        hero_id: player.hero_id,
+        start_time_seconds: stratz_match.start_date_time,
+        version: None,
+        kills: player.kills,
+        deaths: player.deaths,
+        assists: player.assists,
+        average_rank: stratz_match.rank,
+        skill: None,
+        leaver_status: player.leaver_status.as_deref().and_then(leaver_status_id),
+        party_size: player.party_id.and_then(|id| party_sizes.get(&id).copied()),
+        hero_variant: player.variant,
+        raw: None,
+    })
+}
+
+fn game_mode_id(value: &str) -> Option<i32> {
+    Some(match value {
+        "NONE" => 0,
+        "ALL_PICK" => 1,
+        "CAPTAINS_MODE" => 2,
+        "RANDOM_DRAFT" => 3,
+        "SINGLE_DRAFT" => 4,
+        "ALL_RANDOM" => 5,
+        "INTRO" => 6,
+        "THE_DIRETIDE" => 7,
+        "REVERSE_CAPTAINS_MODE" => 8,
+        "THE_GREEVILING" => 9,
+        "TUTORIAL" => 10,
+        "MID_ONLY" => 11,
+        "LEAST_PLAYED" => 12,
+        "NEW_PLAYER_POOL" => 13,
+        "COMPENDIUM_MATCHMAKING" => 14,
+        "CUSTOM" => 15,
+        "CAPTAINS_DRAFT" => 16,
+        "BALANCED_DRAFT" => 17,
+        "ABILITY_DRAFT" => 18,
+        "EVENT" => 19,
+        "ALL_RANDOM_DEATH_MATCH" => 20,
+        "SOLO_MID" => 21,
+        "ALL_PICK_RANKED" => 22,
+        "TURBO" => 23,
+        "MUTATION" => 24,
+        _ => return None,
+    })
+}
+
+fn lobby_type_id(value: &str) -> Option<i32> {
+    Some(match value {
+        "UNRANKED" => 0,
+        "PRACTICE" => 1,
+        "TOURNAMENT" => 2,
+        "TUTORIAL" => 3,
+        "COOP_VS_AI" => 4,
+        "TEAM_MATCH" => 5,
+        "SOLO_QUEUE" => 6,
+        "RANKED" => 7,
+        "SOLO_MID" => 8,
+        "BATTLE_CUP" => 9,
+        "EVENT" => 12,
+        _ => return None,
+    })
+}
+
+fn leaver_status_id(value: &str) -> Option<i32> {
+    Some(match value {
+        "NONE" => 0,
+        "DISCONNECTED" => 1,
+        "DISCONNECTED_TOO_LONG" => 2,
+        "ABANDONED" => 3,
+        "AFK" => 4,
+        "NEVER_CONNECTED" => 5,
+        "NEVER_CONNECTED_TOO_LONG" => 6,
+        _ => return None,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::{BufRead, BufReader, Read, Write};
+    use std::net::{TcpListener, TcpStream};
+    use std::thread::JoinHandle;
+
+    use super::*;
+
+    const MATCHES_RESPONSE: &str = r#"{
+      "data": {
+        "player": {
+          "matches": [
+            {
+              "id": 7000000001,
+              "didRadiantWin": false,
+              "durationSeconds": 2400,
+              "startDateTime": 1700000000,
+              "gameMode": "ALL_PICK_RANKED",
+              "lobbyType": "SOLO_QUEUE",
+              "rank": 75,
+              "players": [
+                { "steamAccountId": 1, "isRadiant": true, "playerSlot": 0, "heroId": 2 },
+                { "steamAccountId": 42, "isRadiant": false, "playerSlot": 130, "heroId": 14,
+                  "kills": 9, "deaths": 3, "assists": 12, "leaverStatus": "NONE",
+                  "partyId": 5, "variant": 1 },
+                { "steamAccountId": 43, "isRadiant": false, "playerSlot": 128, "heroId": 8,
+                  "partyId": 5 }
+              ]
+            }
+          ]
+        }
+      }
+    }"#;
+
+    /// Answers a single request with `body` and returns what was sent.
+    fn serve_once(body: &'static str) -> (String, JoinHandle<String>) {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let url = format!("http://{}/graphql", listener.local_addr().unwrap());
+        let handle = std::thread::spawn(move || {
+            let (mut stream, _) = listener.accept().unwrap();
+            let request = read_request(&stream);
+            let response = format!(
+                "HTTP/1.1 200 OK\r\nContent-Type: application/json\r\n\
+                 Content-Length: {}\r\nConnection: close\r\n\r\n{}",
+                body.len(),
+                body
+            );
+            stream.write_all(response.as_bytes()).unwrap();
+            request
+        });
+        (url, handle)
+    }
+
+    fn read_request(stream: &TcpStream) -> String {
+        let mut reader = BufReader::new(stream);
+        let mut request = String::new();
+        let mut content_length = 0;
+        loop {
+            let mut line = String::new();
+            reader.read_line(&mut line).unwrap();
+            if line == "\r\n" {
+                break;
+            }
+            if let Some(value) = line.to_ascii_lowercase().strip_prefix("content-length:") {
+                content_length = value.trim().parse().unwrap();
+            }
+            request.push_str(&line);
+        }
+        let mut body = vec![0; content_length];
+        reader.read_exact(&mut body).unwrap();
+        request + &String::from_utf8(body).unwrap()
+    }
+
+    #[tokio::test]
+    async fn player_matches_round_trip_against_mock_server() {
+        let (url, server) = serve_once(MATCHES_RESPONSE);
+        std::env::set_var("DOTACORD_TEST_STRATZ_KEY", "test-key");
+        let source = StratzSource::new(&StratzConfig {
+            api_key_var: "DOTACORD_TEST_STRATZ_KEY".to_string(),
+            base_url: Some(url),
+            requests_per_minute: 60,
+        })
+        .unwrap();
+
+        let query = PlayerMatchesQuery {
+            days: None,
+            limit: Some(10),
+        };
+        let matches = source.player_matches(42, query).await.unwrap();
+
+        let request = server.join().unwrap();
+        assert!(request.to_ascii_lowercase().contains("authorization: bearer test-key"));
+        assert!(request.contains(r#""steamAccountId":42"#));
+
+        assert_eq!(matches.len(), 1);
+        let api_match = &matches[0];
+        assert_eq!(api_match.match_id, 7000000001);
+        assert_eq!(api_match.player_slot, Some(130));
+        assert_eq!(api_match.radiant_win, Some(false));
+        assert_eq!(api_match.game_mode, Some(22));
+        assert_eq!(api_match.lobby_type, Some(6));
+        assert_eq!(api_match.hero_id, Some(14));
+        assert_eq!(api_match.start_time_seconds, Some(1700000000));
+        assert_eq!(api_match.kills, Some(9));
+        assert_eq!(api_match.deaths, Some(3));
+        assert_eq!(api_match.assists, Some(12));
+        assert_eq!(api_match.leaver_status, Some(0));
+        assert_eq!(api_match.party_size, Some(2));
+        assert_eq!(api_match.hero_variant, Some(1));
+    }
+
+    #[test]
+    fn dire_slots_keep_their_position() {
+        let dire_match = |slot: i32| StratzMatch {
+            id: 1,
+            did_radiant_win: None,
+            duration_seconds: None,
+            start_date_time: None,
+            game_mode: None,
+            lobby_type: None,
+            rank: None,
+            players: vec![StratzMatchPlayer {
+                steam_account_id: Some(42),
+                is_radiant: Some(false),
+                player_slot: Some(slot),
+                hero_id: None,
+                kills: None,
+                deaths: None,
+                assists: None,
+                leaver_status: None,
+                party_id: None,
+                variant: None,
+            }],
+        };
+
+        for (slot, expected) in [(128, 128), (130, 130), (132, 132), (5, 128), (7, 130), (9, 132)] {
+            let api_match = to_api_match(dire_match(slot), 42).unwrap();
+            assert_eq!(api_match.player_slot, Some(expected), "STRATZ slot {slot}");
+        }
+    }
+}
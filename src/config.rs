@@ -22,6 +22,10 @@ struct FileConfig {
     pub flip_countdown_duration_sec: u64,
     pub countdown_offset_ms: u64,
     pub anthropic: AnthropicConfig,
+    pub opendota: OpenDotaConfig,
+    pub match_source: MatchSourceConfig,
+    pub leaderboard: LeaderboardConfig,
+    pub backup: BackupConfig,
 }
 
 #[derive(Debug, Deserialize, Clone)]
@@ -41,6 +45,10 @@ pub struct SchedulerConfig {
     pub auto_reload: AutoReloadConfig,
     pub weekly_leaderboard: WeeklyLeaderboardConfig,
     pub monthly_leaderboard: MonthlyLeaderboardConfig,
+    pub match_details: MatchDetailsConfig,
+    pub parse_jobs: ParseJobsConfig,
+    pub hero_sync: HeroSyncConfig,
+    pub backup: BackupTaskConfig,
 }
 
 #[derive(Debug, Deserialize, Clone)]
@@ -63,6 +71,40 @@ pub struct MonthlyLeaderboardConfig {
     pub minute: u8,
 }
 
+#[derive(Debug, Deserialize, Clone)]
+pub struct MatchDetailsConfig {
+    pub enabled: bool,
+    /// Max `/matches/{id}` requests per scheduler tick.
+    pub batch_size: usize,
+}
+
+#[derive(Debug, Deserialize, Clone)]
+pub struct ParseJobsConfig {
+    pub enabled: bool,
+    /// Max jobs submitted and max jobs polled per scheduler tick.
+    pub batch_size: u64,
+    pub max_attempts: i32,
+    /// Only matches newer than this are sent for parsing.
+    pub max_match_age_days: i64,
+    pub timeout_mins: i64,
+    /// Failed jobs for matches still within `max_match_age_days` are queued again after this.
+    pub retry_failed_after_hours: i64,
+}
+
+#[derive(Debug, Deserialize, Clone)]
+pub struct HeroSyncConfig {
+    pub enabled: bool,
+    /// Syncs on the hour, every this many hours (UTC).
+    pub interval_hours: u32,
+}
+
+#[derive(Debug, Deserialize, Clone)]
+pub struct BackupTaskConfig {
+    pub enabled: bool,
+    /// Hour of the daily backup (UTC).
+    pub hour: u32,
+}
+
 #[derive(Debug, Deserialize, Clone)]
 pub struct CooldownsConfig {
     pub user_refresh_min: u64,
@@ -91,6 +133,77 @@ pub struct AnthropicConfig {
     pub add_players_context: bool,
 }
 
+#[derive(Clone, Debug, Deserialize)]
+pub struct OpenDotaConfig {
+    /// Env var holding the premium API key. Requests go out anonymously when unset.
+    pub api_key_var: Option<String>,
+    /// Overrides `https://api.opendota.com/api`, e.g. to point at a local stand-in.
+    pub base_url: Option<String>,
+    pub requests_per_minute: u32,
+    pub premium_requests_per_minute: u32,
+    /// How many players a server refresh reloads at once.
+    pub reload_concurrency: usize,
+    pub circuit_breaker: CircuitBreakerConfig,
+}
+
+#[derive(Clone, Copy, Debug, Deserialize)]
+pub struct CircuitBreakerConfig {
+    /// Consecutive server errors or timeouts before requests stop going out.
+    pub failure_threshold: u32,
+    pub probe_interval_secs: u64,
+}
+
+#[derive(Clone, Debug, Deserialize)]
+pub struct LeaderboardConfig {
+    /// Whether matches a player abandoned still count towards their win rates.
+    pub count_abandons_in_win_rates: bool,
+    pub abandons_section: bool,
+}
+
+#[derive(Clone, Debug, Deserialize)]
+pub struct BackupConfig {
+    pub directory: PathBuf,
+    /// Newest snapshot of each of the last `keep_daily` days with one is kept.
+    pub keep_daily: usize,
+    /// Newest snapshot of each of the last `keep_weekly` ISO weeks with one is kept.
+    pub keep_weekly: usize,
+}
+
+#[derive(Clone, Copy, Debug, Deserialize, PartialEq, Eq, Hash)]
+#[serde(rename_all = "snake_case")]
+pub enum MatchSourceKind {
+    OpenDota,
+    Stratz,
+    /// OpenDota and STRATZ matches merged on `match_id`.
+    Combined,
+    Fixture,
+}
+
+#[derive(Clone, Debug, Deserialize)]
+pub struct MatchSourceConfig {
+    pub kind: MatchSourceKind,
+    /// Directory of recorded responses, required when `kind = "fixture"`.
+    pub fixture_dir: Option<PathBuf>,
+    /// Required when any server uses `stratz` or `combined`.
+    pub stratz: Option<StratzConfig>,
+    #[serde(default)]
+    pub server_overrides: Vec<MatchSourceOverride>,
+}
+
+#[derive(Clone, Debug, Deserialize)]
+pub struct StratzConfig {
+    pub api_key_var: String,
+    /// Overrides `https://api.stratz.com/graphql`, e.g. to point at a local mock.
+    pub base_url: Option<String>,
+    pub requests_per_minute: u32,
+}
+
+#[derive(Clone, Debug, Deserialize)]
+pub struct MatchSourceOverride {
+    pub server_id: i64,
+    pub kind: MatchSourceKind,
+}
+
 #[derive(Clone, Debug)]
 #[allow(dead_code)]
 pub struct AppConfig {
@@ -108,6 +221,10 @@ pub struct AppConfig {
     pub flip_countdown_duration_sec: u64,
     pub countdown_offset_ms: u64,
     pub anthropic: AnthropicConfig,
+    pub opendota: OpenDotaConfig,
+    pub match_source: MatchSourceConfig,
+    pub leaderboard: LeaderboardConfig,
+    pub backup: BackupConfig,
 }
 
 fn expand_tilde(path: &str) -> Result<PathBuf, Box<dyn std::error::Error + Send + Sync>> {
@@ -137,8 +254,14 @@ pub fn load_config() -> Result<AppConfig, Box<dyn std::error::Error + Send + Syn
     let cfg: FileConfig = toml::from_str(&s)?;
 
     let database_path = expand_tilde(&cfg.database_path)?;
-    if !database_path.exists() || !database_path.is_file() {
-        return Err(format!("Database file does not exist: {}", &cfg.database_path).into());
+    // A missing database is created and migrated at startup, only its directory has to exist
+    if let Some(parent) = database_path.parent() {
+        if !parent.as_os_str().is_empty() && !parent.exists() {
+            return Err(format!("Database directory does not exist: {}", parent.display()).into());
+        }
+    }
+    if database_path.exists() && !database_path.is_file() {
+        return Err(format!("Database path exists but is not a file: {}", &cfg.database_path).into());
     }
 
     // let api_key = env::var(&cfg.api_key_var).map_err(|e| {
@@ -164,6 +287,20 @@ pub fn load_config() -> Result<AppConfig, Box<dyn std::error::Error + Send + Syn
         flip_countdown_duration_sec: cfg.flip_countdown_duration_sec,
         countdown_offset_ms: cfg.countdown_offset_ms,
         anthropic: cfg.anthropic,
+        opendota: cfg.opendota,
+        match_source: MatchSourceConfig {
+            fixture_dir: cfg
+                .match_source
+                .fixture_dir
+                .map(|dir| expand_tilde(&dir.to_string_lossy()))
+                .transpose()?,
+            ..cfg.match_source
+        },
+        leaderboard: cfg.leaderboard,
+        backup: BackupConfig {
+            directory: expand_tilde(&cfg.backup.directory.to_string_lossy())?,
+            ..cfg.backup
+        },
     })
 }
 
@@ -0,0 +1,34 @@
+use sea_orm::sea_query::OnConflict;
+use sea_orm::*;
+
+use crate::database::database_access;
+use crate::database::entities::{api_cache_entry, ApiCacheEntry};
+use crate::Error;
+
+pub use api_cache_entry::Model as ApiCacheEntryModel;
+
+pub async fn query_entry(cache_key: &str) -> Result<Option<ApiCacheEntryModel>, Error> {
+    let txn = database_access::get_transaction().await?;
+    let row = ApiCacheEntry::find_by_id(cache_key.to_string()).one(&txn).await?;
+    Ok(row)
+}
+
+pub async fn upsert_entry(entry: ApiCacheEntryModel) -> Result<(), Error> {
+    let txn = database_access::get_transaction().await?;
+
+    let active: api_cache_entry::ActiveModel = entry.into();
+    ApiCacheEntry::insert(active)
+        .on_conflict(
+            OnConflict::column(api_cache_entry::Column::CacheKey)
+                .update_columns([
+                    api_cache_entry::Column::Payload,
+                    api_cache_entry::Column::FetchedAt,
+                ])
+                .to_owned(),
+        )
+        .exec(&txn)
+        .await?;
+
+    txn.commit().await?;
+    Ok(())
+}
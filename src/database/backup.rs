@@ -0,0 +1,205 @@
+use std::collections::HashSet;
+use std::path::{Path, PathBuf};
+
+use chrono::{Datelike, NaiveDateTime, Utc};
+use sea_orm::{ConnectOptions, ConnectionTrait, Database, DbBackend, Statement};
+use tracing::{info, warn};
+
+use crate::config::BackupConfig;
+use crate::database::database_access;
+use crate::Error;
+
+const SNAPSHOT_PREFIX: &str = "dotacord-";
+const SNAPSHOT_TIME_FORMAT: &str = "%Y%m%d-%H%M%S";
+
+struct Snapshot {
+    path: PathBuf,
+    taken_at: NaiveDateTime,
+}
+
+/// Takes a verified snapshot of the live database, then prunes snapshots outside the retention.
+pub async fn create_backup(config: &BackupConfig) -> Result<PathBuf, Error> {
+    let path = create_snapshot(&config.directory).await?;
+    prune_snapshots(config)?;
+    Ok(path)
+}
+
+/// `VACUUM INTO` reads a consistent snapshot while the bot keeps writing. It goes to a temporary
+/// file and only gets its final name once `PRAGMA integrity_check` passes.
+async fn create_snapshot(directory: &Path) -> Result<PathBuf, Error> {
+    std::fs::create_dir_all(directory)?;
+
+    let name = format!(
+        "{}{}.db",
+        SNAPSHOT_PREFIX,
+        Utc::now().format(SNAPSHOT_TIME_FORMAT)
+    );
+    let path = directory.join(&name);
+    let tmp_path = directory.join(format!("{}.tmp", name));
+    if tmp_path.exists() {
+        std::fs::remove_file(&tmp_path)?;
+    }
+
+    database_access::get_connection()?
+        .execute(Statement::from_sql_and_values(
+            DbBackend::Sqlite,
+            "VACUUM INTO ?",
+            [tmp_path.to_string_lossy().to_string().into()],
+        ))
+        .await?;
+
+    if let Err(e) = check_integrity(&tmp_path).await {
+        std::fs::remove_file(&tmp_path)?;
+        return Err(e);
+    }
+    std::fs::rename(&tmp_path, &path)?;
+
+    info!(path = %path.display(), "Database snapshot created");
+    Ok(path)
+}
+
+async fn check_integrity(path: &Path) -> Result<(), Error> {
+    let conn = connect(path, "ro").await?;
+    let rows = conn
+        .query_all(Statement::from_string(DbBackend::Sqlite, "PRAGMA integrity_check"))
+        .await?;
+    conn.close().await?;
+
+    let messages = rows
+        .iter()
+        .map(|row| row.try_get_by_index::<String>(0))
+        .collect::<Result<Vec<_>, _>>()?;
+    if messages != ["ok"] {
+        return Err(format!(
+            "Integrity check failed for {}: {}",
+            path.display(),
+            messages.join("; ")
+        )
+        .into());
+    }
+    Ok(())
+}
+
+async fn connect(path: &Path, mode: &str) -> Result<sea_orm::DatabaseConnection, Error> {
+    let mut opt = ConnectOptions::new(format!("sqlite://{}?mode={}", path.display(), mode));
+    // One connection, so per-connection pragmas apply to every statement
+    opt.max_connections(1)
+        .sqlx_logging(false)
+        .sqlx_logging_level(tracing::log::LevelFilter::Off);
+    Ok(Database::connect(opt).await?)
+}
+
+/// Keeps the newest snapshot of each of the last `keep_daily` days and `keep_weekly` ISO weeks
+/// that have one. The newest snapshot is always kept.
+fn prune_snapshots(config: &BackupConfig) -> Result<(), Error> {
+    let mut snapshots = list_snapshots(&config.directory)?;
+    snapshots.sort_by(|a, b| b.taken_at.cmp(&a.taken_at));
+
+    let mut days = HashSet::new();
+    let mut weeks = HashSet::new();
+    for snapshot in &snapshots {
+        let date = snapshot.taken_at.date();
+        let week = date.iso_week();
+
+        let keep_for_day = days.len() < config.keep_daily.max(1) && days.insert(date);
+        let keep_for_week =
+            weeks.len() < config.keep_weekly && weeks.insert((week.year(), week.week()));
+        if keep_for_day || keep_for_week {
+            continue;
+        }
+
+        match std::fs::remove_file(&snapshot.path) {
+            Ok(()) => info!(path = %snapshot.path.display(), "Pruned database snapshot"),
+            Err(e) => warn!(
+                path = %snapshot.path.display(),
+                error = %e,
+                "Failed to prune database snapshot"
+            ),
+        }
+    }
+    Ok(())
+}
+
+fn list_snapshots(directory: &Path) -> Result<Vec<Snapshot>, Error> {
+    let mut snapshots = Vec::new();
+    for entry in std::fs::read_dir(directory)? {
+        let path = entry?.path();
+        if path.extension().and_then(|e| e.to_str()) != Some("db") {
+            continue;
+        }
+        let taken_at = path
+            .file_stem()
+            .and_then(|s| s.to_str())
+            .and_then(|s| s.strip_prefix(SNAPSHOT_PREFIX))
+            .and_then(|s| NaiveDateTime::parse_from_str(s, SNAPSHOT_TIME_FORMAT).ok());
+        if let Some(taken_at) = taken_at {
+            snapshots.push(Snapshot { path, taken_at });
+        }
+    }
+    Ok(snapshots)
+}
+
+/// Replaces the database with `snapshot`. Must run while the bot is stopped: it refuses if another
+/// connection holds the database open. The current database and its WAL files are moved aside as
+/// `<name>.pre-restore-<time>` rather than deleted.
+pub async fn restore_snapshot(snapshot: &Path, database_path: &Path) -> Result<(), Error> {
+    if !snapshot.is_file() {
+        return Err(format!("Snapshot does not exist: {}", snapshot.display()).into());
+    }
+    check_integrity(snapshot).await?;
+
+    if database_path.exists() {
+        ensure_not_in_use(database_path).await?;
+    }
+
+    // Copy next to the database first, so the swap itself is a rename on the same filesystem
+    let restore_tmp = sidecar(database_path, ".restore-tmp");
+    std::fs::copy(snapshot, &restore_tmp)?;
+    std::fs::OpenOptions::new().write(true).open(&restore_tmp)?.sync_all()?;
+
+    let suffix = format!(".pre-restore-{}", Utc::now().format(SNAPSHOT_TIME_FORMAT));
+    for extra in ["", "-wal", "-shm"] {
+        let live = sidecar(database_path, extra);
+        if live.exists() {
+            let aside = sidecar(database_path, &format!("{}{}", extra, suffix));
+            std::fs::rename(&live, &aside)?;
+            info!(from = %live.display(), to = %aside.display(), "Moved current database aside");
+        }
+    }
+    std::fs::rename(&restore_tmp, database_path)?;
+
+    info!(
+        snapshot = %snapshot.display(),
+        database = %database_path.display(),
+        "Database restored, migrations run on next start"
+    );
+    Ok(())
+}
+
+/// Every open WAL-mode connection holds a shared lock on the database file, so an exclusive lock
+/// only succeeds when nothing else has it open.
+async fn ensure_not_in_use(database_path: &Path) -> Result<(), Error> {
+    let conn = connect(database_path, "rw").await?;
+    conn.execute_unprepared("PRAGMA busy_timeout = 0").await?;
+    conn.execute_unprepared("PRAGMA locking_mode = EXCLUSIVE").await?;
+    let locked = conn.execute_unprepared("BEGIN EXCLUSIVE").await;
+    if locked.is_ok() {
+        conn.execute_unprepared("ROLLBACK").await?;
+    }
+    conn.close().await?;
+
+    locked.map(|_| ()).map_err(|e| {
+        format!(
+            "Database {} is in use, stop the bot before restoring: {}",
+            database_path.display(),
+            e
+        )
+        .into()
+    })
+}
+
+fn sidecar(database_path: &Path, suffix: &str) -> PathBuf {
+    let mut name = database_path.as_os_str().to_owned();
+    name.push(suffix);
+    PathBuf::from(name)
+}
@@ -10,6 +10,7 @@ pub use command_event::Model as CommandEventModel;
 pub enum EventType {
     UserRefresh,
     AdminRefresh,
+    RankAnnouncement,
 }
 
 impl EventType {
@@ -17,6 +18,7 @@ impl EventType {
         match self {
             EventType::UserRefresh => "UserRefresh",
             EventType::AdminRefresh => "AdminRefresh",
+            EventType::RankAnnouncement => "RankAnnouncement",
         }
     }
 }
@@ -6,11 +6,14 @@ use sea_orm::{
     ConnectionTrait, ConnectOptions, Database, DatabaseConnection, DatabaseTransaction,
     TransactionTrait,
 };
+use tokio::sync::{Mutex, MutexGuard};
 use tracing::info;
 
+use crate::database::{migrations, schema_check};
 use crate::Error;
 
 static SEA_ORM_CONNECTION: OnceLock<DatabaseConnection> = OnceLock::new();
+static WRITE_LOCK: Mutex<()> = Mutex::const_new(());
 
 pub async fn init_database(path: &Path) -> Result<(), Error> {
     let url = format!("sqlite://{}?mode=rwc", path.display());
@@ -24,6 +27,8 @@ pub async fn init_database(path: &Path) -> Result<(), Error> {
     let conn = Database::connect(opt).await?;
 
     conn.execute_unprepared("PRAGMA journal_mode=WAL").await?;
+    migrations::run(&conn).await?;
+    schema_check::verify(&conn).await?;
 
     SEA_ORM_CONNECTION.set(conn).map_err(|_already| {
         Box::new(std::io::Error::new(
@@ -37,12 +42,22 @@ pub async fn init_database(path: &Path) -> Result<(), Error> {
 }
 
 pub async fn get_transaction() -> Result<DatabaseTransaction, Error> {
-    let conn = SEA_ORM_CONNECTION.get().ok_or_else(|| {
+    let txn = get_connection()?.begin().await?;
+    Ok(txn)
+}
+
+/// For statements that can't run in a transaction, e.g. `VACUUM INTO`.
+pub(crate) fn get_connection() -> Result<&'static DatabaseConnection, Error> {
+    SEA_ORM_CONNECTION.get().ok_or_else(|| {
         Box::new(std::io::Error::new(
             std::io::ErrorKind::NotFound,
             "Database connection not initialized. Call init_database(...) at startup.",
         )) as Error
-    })?;
-    let txn = conn.begin().await?;
-    Ok(txn)
+    })
+}
+
+/// Held by tasks that write while running concurrently, e.g. parallel player reloads. SQLite can't
+/// upgrade two overlapping read transactions to writes, so one of them would fail as busy.
+pub async fn lock_writes() -> MutexGuard<'static, ()> {
+    WRITE_LOCK.lock().await
 }
@@ -0,0 +1,17 @@
+use sea_orm::entity::prelude::*;
+
+#[derive(Clone, Debug, PartialEq, DeriveEntityModel)]
+#[sea_orm(table_name = "api_cache_entries")]
+pub struct Model {
+    /// The endpoint path and parameters, e.g. `heroStats`.
+    #[sea_orm(primary_key, auto_increment = false)]
+    pub cache_key: String,
+    /// The response as JSON.
+    pub payload: String,
+    pub fetched_at: i64,
+}
+
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {}
+
+impl ActiveModelBehavior for ActiveModel {}
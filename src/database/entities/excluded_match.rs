@@ -0,0 +1,20 @@
+use sea_orm::entity::prelude::*;
+
+#[derive(Clone, Debug, PartialEq, DeriveEntityModel)]
+#[sea_orm(table_name = "excluded_matches")]
+pub struct Model {
+    #[sea_orm(primary_key)]
+    pub id: i64,
+    pub match_id: i64,
+    /// `None` excludes the match for every server.
+    pub server_id: Option<i64>,
+    pub reason: String,
+    /// Discord user id of the admin, `None` when excluded from the command line.
+    pub excluded_by: Option<i64>,
+    pub excluded_at: i64,
+}
+
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {}
+
+impl ActiveModelBehavior for ActiveModel {}
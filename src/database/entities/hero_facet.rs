@@ -0,0 +1,31 @@
+use sea_orm::entity::prelude::*;
+
+#[derive(Clone, Debug, PartialEq, DeriveEntityModel)]
+#[sea_orm(table_name = "hero_facets")]
+pub struct Model {
+    #[sea_orm(primary_key, auto_increment = false)]
+    pub hero_id: i32,
+    /// Matches `player_matches.hero_variant`.
+    #[sea_orm(primary_key, auto_increment = false)]
+    pub variant: i32,
+    pub name: String,
+    pub title: String,
+}
+
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {
+    #[sea_orm(
+        belongs_to = "super::hero::Entity",
+        from = "Column::HeroId",
+        to = "super::hero::Column::HeroId"
+    )]
+    Hero,
+}
+
+impl Related<super::hero::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::Hero.def()
+    }
+}
+
+impl ActiveModelBehavior for ActiveModel {}
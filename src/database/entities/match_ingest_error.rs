@@ -0,0 +1,35 @@
+use sea_orm::entity::prelude::*;
+
+#[derive(Clone, Debug, PartialEq, DeriveEntityModel)]
+#[sea_orm(table_name = "match_ingest_errors")]
+pub struct Model {
+    #[sea_orm(primary_key, auto_increment = false)]
+    pub match_id: i64,
+    #[sea_orm(primary_key, auto_increment = false)]
+    pub player_id: i64,
+    /// The `MapperError` variant, e.g. `UnknownHero`.
+    pub error_kind: String,
+    pub error_message: String,
+    /// The rejected `ApiPlayerMatch`, kept so the match can be re-mapped without refetching.
+    pub raw_json: String,
+    pub created_at: i64,
+    pub updated_at: i64,
+}
+
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {
+    #[sea_orm(
+        belongs_to = "super::player::Entity",
+        from = "Column::PlayerId",
+        to = "super::player::Column::PlayerId"
+    )]
+    Player,
+}
+
+impl Related<super::player::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::Player.def()
+    }
+}
+
+impl ActiveModelBehavior for ActiveModel {}
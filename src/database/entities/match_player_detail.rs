@@ -0,0 +1,47 @@
+use sea_orm::entity::prelude::*;
+
+/// Per-player metrics from `/matches/{match_id}`. Metric columns are `None` when OpenDota
+/// had no data for the player, so the row still marks the match as fetched.
+#[derive(Clone, Debug, PartialEq, DeriveEntityModel)]
+#[sea_orm(table_name = "match_player_details")]
+pub struct Model {
+    #[sea_orm(primary_key, auto_increment = false)]
+    pub match_id: i64,
+    #[sea_orm(primary_key, auto_increment = false)]
+    pub player_id: i64,
+    pub gpm: Option<i32>,
+    pub xpm: Option<i32>,
+    pub last_hits: Option<i32>,
+    pub denies: Option<i32>,
+    pub hero_damage: Option<i32>,
+    pub tower_damage: Option<i32>,
+    pub hero_healing: Option<i32>,
+    pub net_worth: Option<i32>,
+    pub item_0: Option<i32>,
+    pub item_1: Option<i32>,
+    pub item_2: Option<i32>,
+    pub item_3: Option<i32>,
+    pub item_4: Option<i32>,
+    pub item_5: Option<i32>,
+    pub item_neutral: Option<i32>,
+    pub is_parsed: bool,
+    pub fetched_at: i64,
+}
+
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {
+    #[sea_orm(
+        belongs_to = "super::player::Entity",
+        from = "Column::PlayerId",
+        to = "super::player::Column::PlayerId"
+    )]
+    Player,
+}
+
+impl Related<super::player::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::Player.def()
+    }
+}
+
+impl ActiveModelBehavior for ActiveModel {}
@@ -1,19 +1,39 @@
+pub mod api_cache_entry;
 pub mod chat_message;
 pub mod command_event;
+pub mod excluded_match;
 pub mod hero;
+pub mod hero_facet;
 pub mod hero_nickname;
+pub mod match_ingest_error;
+pub mod match_player_detail;
+pub mod parse_job;
+pub mod patch;
 pub mod player;
 pub mod player_match;
+pub mod player_profile;
 pub mod player_rule;
 pub mod player_server;
+pub mod player_sync_state;
+pub mod rank_history;
 pub mod server;
 
+pub use api_cache_entry::Entity as ApiCacheEntry;
 pub use chat_message::Entity as ChatMessage;
 pub use command_event::Entity as CommandEvent;
+pub use excluded_match::Entity as ExcludedMatch;
 pub use hero::Entity as Hero;
+pub use hero_facet::Entity as HeroFacet;
 pub use hero_nickname::Entity as HeroNickname;
+pub use match_ingest_error::Entity as MatchIngestError;
+pub use match_player_detail::Entity as MatchPlayerDetail;
+pub use parse_job::Entity as ParseJob;
+pub use patch::Entity as Patch;
 pub use player::Entity as Player;
 pub use player_match::Entity as PlayerMatch;
+pub use player_profile::Entity as PlayerProfile;
 pub use player_rule::Entity as PlayerRule;
 pub use player_server::Entity as PlayerServer;
+pub use player_sync_state::Entity as PlayerSyncState;
+pub use rank_history::Entity as RankHistory;
 pub use server::Entity as Server;
@@ -20,6 +20,11 @@ pub struct Model {
 
     pub game_mode: i32,
     pub lobby_type: i32,
+    /// OpenDota `leaver_status`, 0 when the player stayed. See `types::is_abandon`.
+    pub leaver_status: i32,
+    pub version: Option<i32>,
+    /// 1-based facet index into the hero's facets, see `heroes_db::query_facets`.
+    pub hero_variant: Option<i32>,
 }
 
 #[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
@@ -11,11 +11,16 @@ pub struct Model {
     pub is_sub_week: i32,
     pub is_sub_month: i32,
     pub is_sub_reload: i32,
+    pub is_sub_rank: i32,
     pub weekly_day: Option<i32>,
     pub weekly_hour: Option<i32>,
     pub monthly_week: Option<i32>,
     pub monthly_weekday: Option<i32>,
     pub monthly_hour: Option<i32>,
+    /// Comma-separated game mode ids counted by this server, `None` for the defaults.
+    pub game_modes: Option<String>,
+    /// Comma-separated lobby type ids counted by this server, `None` for the defaults.
+    pub lobby_types: Option<String>,
 }
 
 #[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
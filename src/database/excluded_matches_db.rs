@@ -0,0 +1,87 @@
+use std::collections::HashSet;
+
+use chrono::Utc;
+use sea_orm::*;
+
+use crate::database::database_access;
+use crate::database::entities::{excluded_match, ExcludedMatch};
+use crate::Error;
+
+pub use excluded_match::Model as ExcludedMatchModel;
+
+fn server_condition(server_id: Option<i64>) -> Condition {
+    match server_id {
+        Some(id) => Condition::all().add(excluded_match::Column::ServerId.eq(id)),
+        None => Condition::all().add(excluded_match::Column::ServerId.is_null()),
+    }
+}
+
+/// Excludes the match for `server_id`, or for every server when `None`.
+/// Returns `false` if it was already excluded in that scope.
+pub async fn insert_exclusion(
+    match_id: i64,
+    server_id: Option<i64>,
+    reason: &str,
+    excluded_by: Option<i64>,
+) -> Result<bool, Error> {
+    let txn = database_access::get_transaction().await?;
+
+    let existing = ExcludedMatch::find()
+        .filter(excluded_match::Column::MatchId.eq(match_id))
+        .filter(server_condition(server_id))
+        .one(&txn)
+        .await?;
+    if existing.is_some() {
+        return Ok(false);
+    }
+
+    let exclusion = excluded_match::ActiveModel {
+        id: NotSet,
+        match_id: Set(match_id),
+        server_id: Set(server_id),
+        reason: Set(reason.to_string()),
+        excluded_by: Set(excluded_by),
+        excluded_at: Set(Utc::now().timestamp()),
+    };
+    ExcludedMatch::insert(exclusion).exec(&txn).await?;
+
+    txn.commit().await?;
+    Ok(true)
+}
+
+/// Returns `false` if the match wasn't excluded in that scope.
+pub async fn delete_exclusion(match_id: i64, server_id: Option<i64>) -> Result<bool, Error> {
+    let txn = database_access::get_transaction().await?;
+    let result = ExcludedMatch::delete_many()
+        .filter(excluded_match::Column::MatchId.eq(match_id))
+        .filter(server_condition(server_id))
+        .exec(&txn)
+        .await?;
+    txn.commit().await?;
+    Ok(result.rows_affected > 0)
+}
+
+/// Matches excluded for every server. These are skipped when importing.
+pub async fn query_global_excluded_ids() -> Result<HashSet<i64>, Error> {
+    let txn = database_access::get_transaction().await?;
+    let rows = ExcludedMatch::find()
+        .filter(excluded_match::Column::ServerId.is_null())
+        .all(&txn)
+        .await?;
+    Ok(rows.into_iter().map(|r| r.match_id).collect())
+}
+
+/// Exclusions that apply to the server, both its own and global ones. Newest first.
+pub async fn query_exclusions_for_server(server_id: i64) -> Result<Vec<ExcludedMatchModel>, Error> {
+    let txn = database_access::get_transaction().await?;
+    let rows = ExcludedMatch::find()
+        .filter(
+            Condition::any()
+                .add(excluded_match::Column::ServerId.eq(server_id))
+                .add(excluded_match::Column::ServerId.is_null()),
+        )
+        .order_by_desc(excluded_match::Column::ExcludedAt)
+        .all(&txn)
+        .await?;
+    Ok(rows)
+}
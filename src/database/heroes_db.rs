@@ -1,14 +1,16 @@
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 
 use sea_orm::*;
 
+use crate::api::open_dota_api::{ApiHero, ApiHeroAbilities};
 use crate::database::database_access;
-use crate::database::entities::{hero, hero_nickname, Hero, HeroNickname};
+use crate::database::entities::{hero, hero_facet, hero_nickname, Hero, HeroFacet, HeroNickname};
 use crate::Error;
 
 pub use hero::Model as HeroModel;
+pub use hero_facet::Model as HeroFacetModel;
 
-#[derive(Debug)]
+#[derive(Debug, Clone, Copy, poise::ChoiceParameter)]
 pub enum Position {
     Carry,
     Mid,
@@ -16,6 +18,17 @@ pub enum Position {
     Support,
 }
 
+impl Position {
+    pub fn applies_to(self, hero: &HeroModel) -> bool {
+        match self {
+            Position::Carry => hero.is_carry,
+            Position::Mid => hero.is_mid,
+            Position::Offlane => hero.is_offlane,
+            Position::Support => hero.is_support,
+        }
+    }
+}
+
 pub struct HeroLookup {
     heroes: HashMap<i32, HeroModel>,
     nicknames: HashMap<i32, Vec<String>>,
@@ -37,6 +50,10 @@ impl HeroLookup {
         Ok(Self { heroes, nicknames })
     }
 
+    pub fn get(&self, hero_id: i32) -> Option<&HeroModel> {
+        self.heroes.get(&hero_id)
+    }
+
     pub fn get_name(&self, hero_id: i32) -> Option<&str> {
         self.heroes.get(&hero_id).map(|h| h.name.as_str())
     }
@@ -125,6 +142,99 @@ pub async fn update_hero_position(
     Ok(())
 }
 
+/// Inserts heroes missing from the table and picks up renames. Position flags on existing heroes
+/// are curated by hand and nicknames live in their own table, so neither is touched.
+/// New heroes get carry/support from their OpenDota roles, like `scripts/gen_heroes_sql.py`.
+/// Returns the heroes that were added.
+pub async fn sync_heroes(api_heroes: &[ApiHero]) -> Result<Vec<HeroModel>, Error> {
+    let txn = database_access::get_transaction().await?;
+    let existing: HashMap<i32, HeroModel> = Hero::find()
+        .all(&txn)
+        .await?
+        .into_iter()
+        .map(|h| (h.hero_id, h))
+        .collect();
+
+    let mut added = Vec::new();
+    for api_hero in api_heroes {
+        match existing.get(&api_hero.id) {
+            Some(hero) if hero.name == api_hero.localized_name => {}
+            Some(_) => {
+                let active = hero::ActiveModel {
+                    hero_id: Set(api_hero.id),
+                    name: Set(api_hero.localized_name.clone()),
+                    ..Default::default()
+                };
+                Hero::update(active).exec(&txn).await?;
+            }
+            None => {
+                let has_role = |role: &str| api_hero.roles.iter().any(|r| r == role);
+                let hero = HeroModel {
+                    hero_id: api_hero.id,
+                    name: api_hero.localized_name.clone(),
+                    is_carry: has_role("Carry"),
+                    is_mid: false,
+                    is_offlane: false,
+                    is_support: has_role("Support"),
+                };
+                let active: hero::ActiveModel = hero.clone().into();
+                Hero::insert(active).exec(&txn).await?;
+                added.push(hero);
+            }
+        }
+    }
+
+    txn.commit().await?;
+    Ok(added)
+}
+
+/// Replaces the facet catalog. Facets are looked up by internal hero name, so heroes
+/// missing from `api_heroes` or the heroes table are skipped. Returns how many facets were stored.
+pub async fn sync_facets(
+    api_heroes: &[ApiHero],
+    abilities: &HashMap<String, ApiHeroAbilities>,
+) -> Result<usize, Error> {
+    let txn = database_access::get_transaction().await?;
+    let known: HashSet<i32> = Hero::find().all(&txn).await?.into_iter().map(|h| h.hero_id).collect();
+
+    let mut rows = Vec::new();
+    for api_hero in api_heroes.iter().filter(|h| known.contains(&h.id)) {
+        let Some(hero_abilities) = abilities.get(&api_hero.name) else {
+            continue;
+        };
+        for (index, facet) in hero_abilities.facets.iter().enumerate() {
+            rows.push(hero_facet::ActiveModel {
+                hero_id: Set(api_hero.id),
+                variant: Set(index as i32 + 1),
+                name: Set(facet.name.clone()),
+                title: Set(facet.title.clone().unwrap_or_else(|| facet.name.clone())),
+            });
+        }
+    }
+
+    // An empty response would wipe the catalog, keep the old one instead
+    if rows.is_empty() {
+        return Ok(0);
+    }
+
+    let count = rows.len();
+    HeroFacet::delete_many().exec(&txn).await?;
+    HeroFacet::insert_many(rows).exec(&txn).await?;
+
+    txn.commit().await?;
+    Ok(count)
+}
+
+pub async fn query_facets(hero_id: i32) -> Result<Vec<HeroFacetModel>, Error> {
+    let txn = database_access::get_transaction().await?;
+    let rows = HeroFacet::find()
+        .filter(hero_facet::Column::HeroId.eq(hero_id))
+        .order_by_asc(hero_facet::Column::Variant)
+        .all(&txn)
+        .await?;
+    Ok(rows)
+}
+
 pub async fn query_nicknames(hero_id: i32) -> Result<Vec<String>, Error> {
     let txn = database_access::get_transaction().await?;
     let rows = HeroNickname::find()
@@ -0,0 +1,217 @@
+use std::collections::HashMap;
+
+use sea_orm::sea_query::OnConflict;
+use sea_orm::*;
+use tracing::warn;
+
+use crate::api::open_dota_api::{ApiMatchDetails, ApiMatchPlayer};
+use crate::database::database_access;
+use crate::database::entities::{match_player_detail, player_match, MatchPlayerDetail, PlayerMatch};
+use crate::Error;
+
+pub use match_player_detail::Model as MatchPlayerDetailModel;
+
+/// A stored match that still has tracked players without a detail row.
+pub struct PendingMatch {
+    pub match_id: i64,
+    /// `(player_id, hero_id)` of each tracked player missing details.
+    pub players: Vec<(i64, i32)>,
+}
+
+/// Maps `/matches/{match_id}` to one row per tracked `(player_id, hero_id)`. Players are matched on
+/// account id, falling back to hero for private profiles. A missing match or player yields empty rows
+/// that still mark the match as fetched.
+pub(crate) fn map_match_details(
+    api_details: Option<&ApiMatchDetails>,
+    match_id: i64,
+    players: &[(i64, i32)],
+    fetched_at: i64,
+) -> Vec<MatchPlayerDetailModel> {
+    let is_parsed = api_details.is_some_and(|d| d.is_parsed());
+    players
+        .iter()
+        .map(|&(player_id, hero_id)| {
+            let api_player = api_details.and_then(|d| {
+                d.players
+                    .iter()
+                    .find(|p| p.account_id == Some(player_id))
+                    .or_else(|| d.players.iter().find(|p| p.hero_id == Some(hero_id)))
+            });
+            if api_details.is_some() && api_player.is_none() {
+                warn!(match_id, player_id, "Player not found in match details");
+            }
+            map_to_match_player_detail(api_player, match_id, player_id, is_parsed, fetched_at)
+        })
+        .collect()
+}
+
+fn map_to_match_player_detail(
+    api_player: Option<&ApiMatchPlayer>,
+    match_id: i64,
+    player_id: i64,
+    is_parsed: bool,
+    fetched_at: i64,
+) -> MatchPlayerDetailModel {
+    let field = |f: fn(&ApiMatchPlayer) -> Option<i32>| api_player.and_then(f);
+    MatchPlayerDetailModel {
+        match_id,
+        player_id,
+        gpm: field(|p| p.gold_per_min),
+        xpm: field(|p| p.xp_per_min),
+        last_hits: field(|p| p.last_hits),
+        denies: field(|p| p.denies),
+        hero_damage: field(|p| p.hero_damage),
+        tower_damage: field(|p| p.tower_damage),
+        hero_healing: field(|p| p.hero_healing),
+        net_worth: field(|p| p.net_worth),
+        item_0: field(|p| p.item_0),
+        item_1: field(|p| p.item_1),
+        item_2: field(|p| p.item_2),
+        item_3: field(|p| p.item_3),
+        item_4: field(|p| p.item_4),
+        item_5: field(|p| p.item_5),
+        item_neutral: field(|p| p.item_neutral),
+        is_parsed,
+        fetched_at,
+    }
+}
+
+/// `player_matches` to `match_player_details` on `(match_id, player_id)`. There's no foreign key
+/// between them, so the relation isn't declared on the entities.
+fn details_relation() -> RelationDef {
+    PlayerMatch::belongs_to(MatchPlayerDetail)
+        .from((player_match::Column::MatchId, player_match::Column::PlayerId))
+        .to((match_player_detail::Column::MatchId, match_player_detail::Column::PlayerId))
+        .into()
+}
+
+/// Player matches without a detail row.
+fn missing_details() -> Select<PlayerMatch> {
+    PlayerMatch::find()
+        .join(JoinType::LeftJoin, details_relation())
+        .filter(match_player_detail::Column::MatchId.is_null())
+}
+
+/// Returns up to `limit` matches missing details, newest first, so fresh games are filled in before the backlog.
+pub async fn query_pending_matches(limit: usize) -> Result<Vec<PendingMatch>, Error> {
+    let txn = database_access::get_transaction().await?;
+
+    let match_ids: Vec<i64> = missing_details()
+        .select_only()
+        .column(player_match::Column::MatchId)
+        .group_by(player_match::Column::MatchId)
+        .order_by_desc(player_match::Column::StartTime.max())
+        .limit(limit as u64)
+        .into_tuple()
+        .all(&txn)
+        .await?;
+    if match_ids.is_empty() {
+        return Ok(vec![]);
+    }
+
+    let rows: Vec<(i64, i64, i32)> = missing_details()
+        .select_only()
+        .column(player_match::Column::MatchId)
+        .column(player_match::Column::PlayerId)
+        .column(player_match::Column::HeroId)
+        .filter(player_match::Column::MatchId.is_in(match_ids.clone()))
+        .into_tuple()
+        .all(&txn)
+        .await?;
+
+    let mut pending: HashMap<i64, Vec<(i64, i32)>> = HashMap::new();
+    for (match_id, player_id, hero_id) in rows {
+        pending.entry(match_id).or_default().push((player_id, hero_id));
+    }
+
+    Ok(match_ids
+        .into_iter()
+        .filter_map(|match_id| {
+            pending
+                .remove(&match_id)
+                .map(|players| PendingMatch { match_id, players })
+        })
+        .collect())
+}
+
+/// Inserts detail rows, ignoring any that already exist so an interrupted batch can simply be re-run.
+pub async fn insert_details(details: Vec<MatchPlayerDetailModel>) -> Result<(), Error> {
+    if details.is_empty() {
+        return Ok(());
+    }
+
+    let txn = database_access::get_transaction().await?;
+    let active_models: Vec<match_player_detail::ActiveModel> =
+        details.into_iter().map(Into::into).collect();
+
+    MatchPlayerDetail::insert_many(active_models)
+        .on_conflict(
+            OnConflict::columns([
+                match_player_detail::Column::MatchId,
+                match_player_detail::Column::PlayerId,
+            ])
+            .do_nothing()
+            .to_owned(),
+        )
+        .do_nothing()
+        .exec(&txn)
+        .await?;
+
+    txn.commit().await?;
+    Ok(())
+}
+
+/// Overwrites the rows for a match, e.g. after a parse adds data that wasn't there before.
+pub async fn replace_details(match_id: i64, details: Vec<MatchPlayerDetailModel>) -> Result<(), Error> {
+    let txn = database_access::get_transaction().await?;
+
+    MatchPlayerDetail::delete_many()
+        .filter(match_player_detail::Column::MatchId.eq(match_id))
+        .exec(&txn)
+        .await?;
+
+    if !details.is_empty() {
+        let active_models: Vec<match_player_detail::ActiveModel> =
+            details.into_iter().map(Into::into).collect();
+        MatchPlayerDetail::insert_many(active_models).exec(&txn).await?;
+    }
+
+    txn.commit().await?;
+    Ok(())
+}
+
+/// Matches since `start_time` without parsed details, either because the stored details came from
+/// an unparsed replay or because details were never fetched.
+pub async fn query_unparsed_match_ids(start_time: i64) -> Result<Vec<i64>, Error> {
+    let txn = database_access::get_transaction().await?;
+
+    let match_ids = PlayerMatch::find()
+        .select_only()
+        .column(player_match::Column::MatchId)
+        .distinct()
+        .join(JoinType::LeftJoin, details_relation())
+        .filter(player_match::Column::StartTime.gte(start_time))
+        .filter(
+            Condition::any()
+                .add(match_player_detail::Column::IsParsed.is_null())
+                .add(match_player_detail::Column::IsParsed.eq(false)),
+        )
+        .into_tuple::<i64>()
+        .all(&txn)
+        .await?;
+
+    Ok(match_ids)
+}
+
+pub async fn query_details(
+    player_id: i64,
+    match_ids: Vec<i64>,
+) -> Result<Vec<MatchPlayerDetailModel>, Error> {
+    let txn = database_access::get_transaction().await?;
+    let rows = MatchPlayerDetail::find()
+        .filter(match_player_detail::Column::PlayerId.eq(player_id))
+        .filter(match_player_detail::Column::MatchId.is_in(match_ids))
+        .all(&txn)
+        .await?;
+    Ok(rows)
+}
@@ -0,0 +1,63 @@
+use sea_orm::sea_query::OnConflict;
+use sea_orm::*;
+
+use crate::database::database_access;
+use crate::database::entities::{match_ingest_error, MatchIngestError};
+use crate::Error;
+
+pub use match_ingest_error::Model as MatchIngestErrorModel;
+
+/// Records a rejected match. Seeing it again refreshes the error but keeps `created_at`.
+pub async fn upsert_error(
+    db: &DatabaseTransaction,
+    error: MatchIngestErrorModel,
+) -> Result<(), Error> {
+    let active: match_ingest_error::ActiveModel = error.into();
+    MatchIngestError::insert(active)
+        .on_conflict(
+            OnConflict::columns([
+                match_ingest_error::Column::MatchId,
+                match_ingest_error::Column::PlayerId,
+            ])
+            .update_columns([
+                match_ingest_error::Column::ErrorKind,
+                match_ingest_error::Column::ErrorMessage,
+                match_ingest_error::Column::RawJson,
+                match_ingest_error::Column::UpdatedAt,
+            ])
+            .to_owned(),
+        )
+        .exec(db)
+        .await?;
+    Ok(())
+}
+
+/// Clears errors for matches that have since been imported.
+pub async fn delete_errors(
+    db: &DatabaseTransaction,
+    player_id: i64,
+    match_ids: Vec<i64>,
+) -> Result<(), Error> {
+    if match_ids.is_empty() {
+        return Ok(());
+    }
+
+    MatchIngestError::delete_many()
+        .filter(match_ingest_error::Column::PlayerId.eq(player_id))
+        .filter(match_ingest_error::Column::MatchId.is_in(match_ids))
+        .exec(db)
+        .await?;
+    Ok(())
+}
+
+pub async fn query_errors_by_player_ids(
+    player_ids: Vec<i64>,
+) -> Result<Vec<MatchIngestErrorModel>, Error> {
+    let txn = database_access::get_transaction().await?;
+    let rows = MatchIngestError::find()
+        .filter(match_ingest_error::Column::PlayerId.is_in(player_ids))
+        .order_by_desc(match_ingest_error::Column::UpdatedAt)
+        .all(&txn)
+        .await?;
+    Ok(rows)
+}
@@ -0,0 +1,168 @@
+use chrono::Utc;
+use sea_orm::{ConnectionTrait, DatabaseConnection, DbBackend, Statement, TransactionTrait};
+use tracing::info;
+
+use crate::Error;
+
+struct Migration {
+    version: i64,
+    name: &'static str,
+    sql: &'static str,
+}
+
+/// Applied in order, each in its own transaction. Never edit a migration once it has shipped,
+/// add a new one instead.
+const MIGRATIONS: &[Migration] = &[
+    Migration {
+        version: 1,
+        name: "initial",
+        sql: include_str!("../../sql/migrations/0001_initial.sql"),
+    },
+    Migration {
+        version: 2,
+        name: "servers_add_is_sub_rank",
+        sql: include_str!("../../sql/migrations/0002_servers_add_is_sub_rank.sql"),
+    },
+    Migration {
+        version: 3,
+        name: "servers_add_match_filter",
+        sql: include_str!("../../sql/migrations/0003_servers_add_match_filter.sql"),
+    },
+    Migration {
+        version: 4,
+        name: "player_matches_add_leaver_status",
+        sql: include_str!("../../sql/migrations/0004_player_matches_add_leaver_status.sql"),
+    },
+    Migration {
+        version: 5,
+        name: "player_matches_add_version_and_hero_variant",
+        sql: include_str!(
+            "../../sql/migrations/0005_player_matches_add_version_and_hero_variant.sql"
+        ),
+    },
+    Migration {
+        version: 6,
+        name: "keys_and_indexes",
+        sql: include_str!("../../sql/migrations/0006_keys_and_indexes.sql"),
+    },
+];
+
+/// Creates a fresh database or brings an existing one up to the latest schema.
+pub(crate) async fn run(conn: &DatabaseConnection) -> Result<(), Error> {
+    // Databases made by the old hand-run scripts have tables but no version yet
+    let legacy =
+        !table_exists(conn, "schema_version").await? && table_exists(conn, "players").await?;
+
+    conn.execute_unprepared(
+        "CREATE TABLE IF NOT EXISTS schema_version
+        (
+            version    INTEGER PRIMARY KEY NOT NULL,
+            name       TEXT    NOT NULL,
+            applied_at INTEGER NOT NULL
+        )",
+    )
+    .await?;
+
+    let current = current_version(conn).await?;
+    let latest = MIGRATIONS.last().map_or(0, |m| m.version);
+    if current > latest {
+        return Err(format!(
+            "Database schema version {} is newer than this build supports ({})",
+            current, latest
+        )
+        .into());
+    }
+    if legacy {
+        info!("Adopting database created before schema migrations");
+    }
+
+    for migration in MIGRATIONS.iter().filter(|m| m.version > current) {
+        apply(conn, migration, legacy).await?;
+    }
+
+    info!(version = latest, "Database schema up to date");
+    Ok(())
+}
+
+async fn apply(
+    conn: &DatabaseConnection,
+    migration: &Migration,
+    legacy: bool,
+) -> Result<(), Error> {
+    info!(version = migration.version, name = migration.name, "Applying migration");
+    let txn = conn.begin().await?;
+
+    for statement in statements(migration.sql) {
+        if let Err(e) = txn.execute_unprepared(&statement).await {
+            // Legacy databases may already have columns the old alter scripts added by hand
+            if legacy && e.to_string().contains("duplicate column name") {
+                info!(
+                    version = migration.version,
+                    statement = statement.as_str(),
+                    "Column already exists, skipping"
+                );
+                continue;
+            }
+            return Err(format!(
+                "Migration {} ({}) failed: {}",
+                migration.version, migration.name, e
+            )
+            .into());
+        }
+    }
+
+    txn.execute(Statement::from_sql_and_values(
+        DbBackend::Sqlite,
+        "INSERT INTO schema_version (version, name, applied_at) VALUES (?, ?, ?)",
+        [
+            migration.version.into(),
+            migration.name.into(),
+            Utc::now().timestamp().into(),
+        ],
+    ))
+    .await?;
+    txn.commit().await?;
+    Ok(())
+}
+
+/// Splits a script into statements. Migrations only use whole-line `--` comments and
+/// no semicolons inside literals, so a plain split is enough.
+fn statements(sql: &str) -> Vec<String> {
+    let without_comments: String = sql
+        .lines()
+        .filter(|line| !line.trim_start().starts_with("--"))
+        .collect::<Vec<_>>()
+        .join("\n");
+
+    without_comments
+        .split(';')
+        .map(str::trim)
+        .filter(|s| !s.is_empty())
+        .map(str::to_string)
+        .collect()
+}
+
+async fn table_exists(conn: &DatabaseConnection, table: &str) -> Result<bool, Error> {
+    let row = conn
+        .query_one(Statement::from_sql_and_values(
+            DbBackend::Sqlite,
+            "SELECT 1 AS found FROM sqlite_master WHERE type = 'table' AND name = ?",
+            [table.into()],
+        ))
+        .await?;
+    Ok(row.is_some())
+}
+
+async fn current_version(conn: &DatabaseConnection) -> Result<i64, Error> {
+    let row = conn
+        .query_one(Statement::from_string(
+            DbBackend::Sqlite,
+            "SELECT MAX(version) AS version FROM schema_version",
+        ))
+        .await?;
+    let version = match row {
+        Some(row) => row.try_get::<Option<i64>>("", "version")?,
+        None => None,
+    };
+    Ok(version.unwrap_or(0))
+}
@@ -1,11 +1,23 @@
+pub mod api_cache_db;
+pub mod backup;
 pub mod chat_messages_db;
 pub mod command_events_db;
 pub mod database_access;
 pub mod entities;
+pub mod excluded_matches_db;
 pub mod heroes_db;
+pub mod match_details_db;
+pub mod match_ingest_errors_db;
+mod migrations;
+pub mod parse_jobs_db;
+pub mod patches_db;
 pub mod player_matches_db;
+pub mod player_profiles_db;
 pub mod player_rules_db;
 pub mod player_servers_db;
+pub mod player_sync_db;
 pub mod players_db;
+pub mod rank_history_db;
+mod schema_check;
 pub mod servers_db;
 pub mod types;
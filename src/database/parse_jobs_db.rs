@@ -0,0 +1,135 @@
+use sea_orm::sea_query::OnConflict;
+use sea_orm::*;
+
+use crate::database::database_access;
+use crate::database::entities::{
+    parse_job, player_match, player_server, ParseJob, PlayerMatch, PlayerServer,
+};
+use crate::Error;
+
+pub use parse_job::Model as ParseJobModel;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ParseJobState {
+    /// Waiting to be submitted to `/request/{match_id}`.
+    Queued,
+    /// Submitted; polled via `/request/{job_id}` until done.
+    Requested,
+    Parsed,
+    Failed,
+}
+
+impl ParseJobState {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ParseJobState::Queued => "Queued",
+            ParseJobState::Requested => "Requested",
+            ParseJobState::Parsed => "Parsed",
+            ParseJobState::Failed => "Failed",
+        }
+    }
+}
+
+/// Queues a parse request, leaving any existing job for the match untouched.
+pub async fn insert_queued_job(match_id: i64, now: i64) -> Result<(), Error> {
+    let txn = database_access::get_transaction().await?;
+
+    let job = parse_job::ActiveModel {
+        match_id: Set(match_id),
+        job_id: Set(None),
+        state: Set(ParseJobState::Queued.as_str().to_string()),
+        attempts: Set(0),
+        created_at: Set(now),
+        updated_at: Set(now),
+        last_error: Set(None),
+    };
+
+    ParseJob::insert(job)
+        .on_conflict(
+            OnConflict::column(parse_job::Column::MatchId)
+                .do_nothing()
+                .to_owned(),
+        )
+        .do_nothing()
+        .exec(&txn)
+        .await?;
+
+    txn.commit().await?;
+    Ok(())
+}
+
+/// Oldest first, so jobs are worked through in the order they were queued.
+pub async fn query_jobs_by_state(
+    state: ParseJobState,
+    limit: u64,
+) -> Result<Vec<ParseJobModel>, Error> {
+    let txn = database_access::get_transaction().await?;
+    let rows = ParseJob::find()
+        .filter(parse_job::Column::State.eq(state.as_str()))
+        .order_by_asc(parse_job::Column::UpdatedAt)
+        .limit(limit)
+        .all(&txn)
+        .await?;
+    Ok(rows)
+}
+
+pub async fn query_jobs_by_match_ids(match_ids: Vec<i64>) -> Result<Vec<ParseJobModel>, Error> {
+    let txn = database_access::get_transaction().await?;
+    let rows = ParseJob::find()
+        .filter(parse_job::Column::MatchId.is_in(match_ids))
+        .order_by_desc(parse_job::Column::UpdatedAt)
+        .all(&txn)
+        .await?;
+    Ok(rows)
+}
+
+/// Jobs for matches played by any player registered on `server_id`.
+pub async fn query_jobs_for_server(server_id: i64) -> Result<Vec<ParseJobModel>, Error> {
+    let txn = database_access::get_transaction().await?;
+    let rows = ParseJob::find()
+        .distinct()
+        .join(
+            JoinType::InnerJoin,
+            ParseJob::belongs_to(PlayerMatch)
+                .from(parse_job::Column::MatchId)
+                .to(player_match::Column::MatchId)
+                .into(),
+        )
+        .join(
+            JoinType::InnerJoin,
+            PlayerMatch::belongs_to(PlayerServer)
+                .from(player_match::Column::PlayerId)
+                .to(player_server::Column::PlayerId)
+                .into(),
+        )
+        .filter(player_server::Column::ServerId.eq(server_id))
+        .order_by_desc(parse_job::Column::UpdatedAt)
+        .all(&txn)
+        .await?;
+    Ok(rows)
+}
+
+pub async fn update_job(
+    match_id: i64,
+    state: ParseJobState,
+    job_id: Option<i64>,
+    attempts: i32,
+    last_error: Option<String>,
+    now: i64,
+) -> Result<(), Error> {
+    let txn = database_access::get_transaction().await?;
+
+    let job = parse_job::ActiveModel {
+        match_id: Set(match_id),
+        job_id: Set(job_id),
+        state: Set(state.as_str().to_string()),
+        attempts: Set(attempts),
+        updated_at: Set(now),
+        last_error: Set(last_error),
+        ..Default::default()
+    };
+    ParseJob::update(job).exec(&txn).await?;
+
+    txn.commit().await?;
+    Ok(())
+}
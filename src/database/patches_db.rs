@@ -0,0 +1,60 @@
+use chrono::DateTime;
+use sea_orm::*;
+use tracing::warn;
+
+use crate::api::open_dota_api::ApiPatch;
+use crate::database::database_access;
+use crate::database::entities::{patch, Patch};
+use crate::Error;
+
+pub use patch::Model as PatchModel;
+
+/// Upserts the patch list. Patches with an unparseable date are skipped.
+/// Returns how many patches were stored.
+pub async fn sync_patches(api_patches: &[ApiPatch]) -> Result<usize, Error> {
+    let txn = database_access::get_transaction().await?;
+
+    let mut count = 0;
+    for api_patch in api_patches {
+        let release_time = match DateTime::parse_from_rfc3339(&api_patch.date) {
+            Ok(date) => date.timestamp(),
+            Err(e) => {
+                warn!(
+                    patch = api_patch.name.as_str(),
+                    date = api_patch.date.as_str(),
+                    error = ?e,
+                    "Skipping patch with invalid date"
+                );
+                continue;
+            }
+        };
+
+        let row = patch::ActiveModel {
+            patch_id: Set(api_patch.id),
+            name: Set(api_patch.name.clone()),
+            release_time: Set(release_time),
+        };
+        Patch::insert(row)
+            .on_conflict(
+                OnConflict::column(patch::Column::PatchId)
+                    .update_columns([patch::Column::Name, patch::Column::ReleaseTime])
+                    .to_owned(),
+            )
+            .exec(&txn)
+            .await?;
+        count += 1;
+    }
+
+    txn.commit().await?;
+    Ok(count)
+}
+
+/// The most recently released patch, if the catalog has been synced.
+pub async fn query_current_patch() -> Result<Option<PatchModel>, Error> {
+    let txn = database_access::get_transaction().await?;
+    let row = Patch::find()
+        .order_by_desc(patch::Column::ReleaseTime)
+        .one(&txn)
+        .await?;
+    Ok(row)
+}
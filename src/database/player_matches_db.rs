@@ -1,9 +1,10 @@
+use sea_orm::sea_query::OnConflict;
 use sea_orm::*;
 
 use crate::api::open_dota_api::ApiPlayerMatch;
 use crate::database::entities::{player_match, PlayerMatch};
 use crate::database::heroes_db::HeroLookup;
-use crate::database::types::{Faction, GameMode, LobbyType, MapperError};
+use crate::database::types::{Faction, GameMode, LobbyType, MapperError, MatchFilter};
 use crate::database::{database_access};
 use crate::Error;
 
@@ -15,9 +16,6 @@ pub(crate) fn map_to_player_match(
     hero_lookup: &HeroLookup,
 ) -> Result<Option<player_match::Model>, MapperError> {
     let match_id = api_match.match_id;
-    if match_id == 1439386853 {
-        return Ok(None);
-    }
     let hero_id = match api_match.hero_id {
         Some(id) if id != 0 => id,
         _ => return Ok(None),
@@ -27,10 +25,6 @@ pub(crate) fn map_to_player_match(
         return Err(MapperError::UnknownHero { hero_id, match_id });
     }
 
-    if matches!(api_match.leaver_status, Some(1 | 2)) {
-        return Ok(None);
-    }
-
     let game_mode_value = api_match.game_mode.ok_or(MapperError::MissingField {
         field: "game_mode",
         match_id,
@@ -51,17 +45,6 @@ pub(crate) fn map_to_player_match(
             match_id,
         })?;
 
-    const RELEVANT_GAME_MODES: [GameMode; 2] = [GameMode::Ranked, GameMode::AllPick];
-    const RELEVANT_LOBBY_TYPES: [LobbyType; 3] = [
-        LobbyType::Unranked,
-        LobbyType::Ranked,
-        LobbyType::RankedSolo,
-    ];
-
-    if !RELEVANT_GAME_MODES.contains(&game_mode) || !RELEVANT_LOBBY_TYPES.contains(&lobby_type) {
-        return Ok(None);
-    }
-
     let start_time = api_match
         .start_time_seconds
         .ok_or(MapperError::MissingField {
@@ -119,16 +102,27 @@ pub(crate) fn map_to_player_match(
         duration,
         game_mode: game_mode.as_i32(),
         lobby_type: lobby_type.as_i32(),
+        leaver_status: api_match.leaver_status.unwrap_or(0),
+        version: api_match.version,
+        hero_variant: api_match.hero_variant,
     }))
 }
 
+/// Returns false when the player already has the match.
 pub async fn insert_player_match(
     db: &DatabaseTransaction,
     player_match: player_match::Model,
-) -> Result<(), Error> {
+) -> Result<bool, Error> {
     let active_model: player_match::ActiveModel = player_match.into();
-    PlayerMatch::insert(active_model).exec(db).await?;
-    Ok(())
+    let inserted = PlayerMatch::insert(active_model)
+        .on_conflict(
+            OnConflict::columns([player_match::Column::MatchId, player_match::Column::PlayerId])
+                .do_nothing()
+                .to_owned(),
+        )
+        .exec_without_returning(db)
+        .await?;
+    Ok(inserted > 0)
 }
 
 pub async fn query_matches_by_player_id(player_id: i64) -> Result<Vec<player_match::Model>, Error> {
@@ -142,6 +136,21 @@ pub async fn query_matches_by_player_id(player_id: i64) -> Result<Vec<player_mat
     Ok(rows)
 }
 
+pub async fn query_matches_since(
+    player_id: i64,
+    start_time: i64,
+) -> Result<Vec<player_match::Model>, Error> {
+    let txn = database_access::get_transaction().await?;
+
+    let rows = PlayerMatch::find()
+        .filter(player_match::Column::PlayerId.eq(player_id))
+        .filter(player_match::Column::StartTime.gte(start_time))
+        .all(&txn)
+        .await?;
+
+    Ok(rows)
+}
+
 pub async fn query_match_by_id(match_id: i64) -> Result<Vec<player_match::Model>, Error> {
     let txn = database_access::get_transaction().await?;
 
@@ -156,12 +165,16 @@ pub async fn query_match_by_id(match_id: i64) -> Result<Vec<player_match::Model>
 pub async fn query_matches_by_hero(
     player_id: i64,
     hero_id: i32,
+    match_filter: &MatchFilter,
 ) -> Result<Vec<player_match::Model>, Error> {
     let txn = database_access::get_transaction().await?;
 
     let rows = PlayerMatch::find()
         .filter(player_match::Column::PlayerId.eq(player_id))
         .filter(player_match::Column::HeroId.eq(hero_id))
+        .filter(player_match::Column::GameMode.is_in(match_filter.game_modes.clone()))
+        .filter(player_match::Column::LobbyType.is_in(match_filter.lobby_types.clone()))
+        .filter(player_match::Column::MatchId.is_not_in(match_filter.excluded_match_ids.clone()))
         .order_by_desc(player_match::Column::StartTime)
         .all(&txn)
         .await?;
@@ -173,12 +186,16 @@ pub async fn query_matches_by_duration(
     player_id: i64,
     start_date: i32,
     end_date: i32,
+    match_filter: &MatchFilter,
 ) -> Result<Vec<player_match::Model>, Error> {
     let txn = database_access::get_transaction().await?;
 
     let rows = PlayerMatch::find()
         .filter(player_match::Column::PlayerId.eq(player_id))
         .filter(player_match::Column::StartTime.between(start_date, end_date))
+        .filter(player_match::Column::GameMode.is_in(match_filter.game_modes.clone()))
+        .filter(player_match::Column::LobbyType.is_in(match_filter.lobby_types.clone()))
+        .filter(player_match::Column::MatchId.is_not_in(match_filter.excluded_match_ids.clone()))
         .all(&txn)
         .await?;
 
@@ -0,0 +1,51 @@
+use std::collections::HashMap;
+
+use sea_orm::sea_query::OnConflict;
+use sea_orm::*;
+
+use crate::database::database_access;
+use crate::database::entities::{player_profile, PlayerProfile};
+use crate::Error;
+
+pub use player_profile::Model as PlayerProfileModel;
+
+pub async fn query_profile(player_id: i64) -> Result<Option<PlayerProfileModel>, Error> {
+    let txn = database_access::get_transaction().await?;
+    let row = PlayerProfile::find_by_id(player_id).one(&txn).await?;
+    Ok(row)
+}
+
+pub async fn query_profiles_by_player_ids(
+    player_ids: Vec<i64>,
+) -> Result<HashMap<i64, PlayerProfileModel>, Error> {
+    let txn = database_access::get_transaction().await?;
+    let rows = PlayerProfile::find()
+        .filter(player_profile::Column::PlayerId.is_in(player_ids))
+        .all(&txn)
+        .await?;
+    Ok(rows.into_iter().map(|p| (p.player_id, p)).collect())
+}
+
+pub async fn upsert_profile(profile: PlayerProfileModel) -> Result<(), Error> {
+    let txn = database_access::get_transaction().await?;
+
+    let active: player_profile::ActiveModel = profile.into();
+    PlayerProfile::insert(active)
+        .on_conflict(
+            OnConflict::column(player_profile::Column::PlayerId)
+                .update_columns([
+                    player_profile::Column::PersonaName,
+                    player_profile::Column::AvatarUrl,
+                    player_profile::Column::RankTier,
+                    player_profile::Column::LeaderboardRank,
+                    player_profile::Column::LastLogin,
+                    player_profile::Column::UpdatedAt,
+                ])
+                .to_owned(),
+        )
+        .exec(&txn)
+        .await?;
+
+    txn.commit().await?;
+    Ok(())
+}
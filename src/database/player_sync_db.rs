@@ -0,0 +1,54 @@
+use sea_orm::sea_query::OnConflict;
+use sea_orm::*;
+
+use crate::database::database_access;
+use crate::database::entities::{player_sync_state, PlayerSyncState};
+use crate::Error;
+
+pub use player_sync_state::Model as PlayerSyncStateModel;
+
+pub async fn query_sync_state(player_id: i64) -> Result<Option<PlayerSyncStateModel>, Error> {
+    let txn = database_access::get_transaction().await?;
+    let row = PlayerSyncState::find_by_id(player_id).one(&txn).await?;
+    Ok(row)
+}
+
+/// Records the newest match seen for a player. `is_full_sync` also stamps `last_full_sync`.
+pub async fn upsert_sync_state(
+    player_id: i64,
+    last_match_id: i64,
+    last_start_time: i64,
+    is_full_sync: bool,
+    now: i64,
+) -> Result<(), Error> {
+    let txn = database_access::get_transaction().await?;
+
+    let active = player_sync_state::ActiveModel {
+        player_id: Set(player_id),
+        last_match_id: Set(last_match_id),
+        last_start_time: Set(last_start_time),
+        last_full_sync: Set(is_full_sync.then_some(now)),
+        updated_at: Set(now),
+    };
+
+    let mut update_columns = vec![
+        player_sync_state::Column::LastMatchId,
+        player_sync_state::Column::LastStartTime,
+        player_sync_state::Column::UpdatedAt,
+    ];
+    if is_full_sync {
+        update_columns.push(player_sync_state::Column::LastFullSync);
+    }
+
+    PlayerSyncState::insert(active)
+        .on_conflict(
+            OnConflict::column(player_sync_state::Column::PlayerId)
+                .update_columns(update_columns)
+                .to_owned(),
+        )
+        .exec(&txn)
+        .await?;
+
+    txn.commit().await?;
+    Ok(())
+}
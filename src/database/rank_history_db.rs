@@ -0,0 +1,94 @@
+use std::collections::HashMap;
+
+use sea_orm::*;
+
+use crate::database::database_access;
+use crate::database::entities::{rank_history, RankHistory};
+use crate::Error;
+
+pub use rank_history::Model as RankHistoryModel;
+
+/// A player's rank before and after a window of history rows.
+#[derive(Debug, Clone)]
+pub struct RankChange {
+    pub player_id: i64,
+    pub previous_rank_tier: Option<i32>,
+    pub rank_tier: Option<i32>,
+    pub leaderboard_rank: Option<i32>,
+}
+
+pub async fn insert_rank(
+    player_id: i64,
+    rank_tier: Option<i32>,
+    leaderboard_rank: Option<i32>,
+    recorded_at: i64,
+) -> Result<(), Error> {
+    let txn = database_access::get_transaction().await?;
+
+    let row = rank_history::ActiveModel {
+        id: NotSet,
+        player_id: Set(player_id),
+        rank_tier: Set(rank_tier),
+        leaderboard_rank: Set(leaderboard_rank),
+        recorded_at: Set(recorded_at),
+    };
+    RankHistory::insert(row).exec(&txn).await?;
+
+    txn.commit().await?;
+    Ok(())
+}
+
+/// Whether any of `player_ids` has a history row recorded after `since`.
+pub async fn has_ranks_since(player_ids: Vec<i64>, since: i64) -> Result<bool, Error> {
+    let txn = database_access::get_transaction().await?;
+    let count = RankHistory::find()
+        .filter(rank_history::Column::PlayerId.is_in(player_ids))
+        .filter(rank_history::Column::RecordedAt.gt(since))
+        .count(&txn)
+        .await?;
+    Ok(count > 0)
+}
+
+/// Compares each player's latest rank recorded after `since` with the last one before it.
+/// Players first seen inside the window, or back where they started, are left out.
+pub async fn query_rank_changes(player_ids: Vec<i64>, since: i64) -> Result<Vec<RankChange>, Error> {
+    let txn = database_access::get_transaction().await?;
+
+    let recent = RankHistory::find()
+        .filter(rank_history::Column::PlayerId.is_in(player_ids))
+        .filter(rank_history::Column::RecordedAt.gt(since))
+        .order_by_asc(rank_history::Column::Id)
+        .all(&txn)
+        .await?;
+
+    let mut latest: HashMap<i64, RankHistoryModel> = HashMap::new();
+    for row in recent {
+        latest.insert(row.player_id, row);
+    }
+
+    let mut changes = Vec::new();
+    for (player_id, current) in latest {
+        let previous = RankHistory::find()
+            .filter(rank_history::Column::PlayerId.eq(player_id))
+            .filter(rank_history::Column::RecordedAt.lte(since))
+            .order_by_desc(rank_history::Column::Id)
+            .one(&txn)
+            .await?;
+
+        let Some(previous) = previous else {
+            continue;
+        };
+        if previous.rank_tier == current.rank_tier {
+            continue;
+        }
+
+        changes.push(RankChange {
+            player_id,
+            previous_rank_tier: previous.rank_tier,
+            rank_tier: current.rank_tier,
+            leaderboard_rank: current.leaderboard_rank,
+        });
+    }
+
+    Ok(changes)
+}
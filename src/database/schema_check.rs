@@ -0,0 +1,142 @@
+use std::collections::{BTreeSet, HashMap};
+
+use sea_orm::*;
+use tracing::{info, warn};
+
+use crate::database::entities::{
+    ApiCacheEntry, ChatMessage, CommandEvent, ExcludedMatch, Hero, HeroFacet, HeroNickname,
+    MatchIngestError, MatchPlayerDetail, ParseJob, Patch, Player, PlayerMatch, PlayerProfile,
+    PlayerRule, PlayerServer, PlayerSyncState, RankHistory, Server,
+};
+use crate::Error;
+
+struct LiveColumn {
+    not_null: bool,
+    has_default: bool,
+    is_primary_key: bool,
+}
+
+/// Compares the live schema against the entities. Missing tables or columns, mismatched primary
+/// keys and required columns the entities never set stop startup; nullability drift is only logged.
+pub(crate) async fn verify(conn: &DatabaseConnection) -> Result<(), Error> {
+    let mut problems = Vec::new();
+
+    check_entity(conn, ApiCacheEntry, &mut problems).await?;
+    check_entity(conn, ChatMessage, &mut problems).await?;
+    check_entity(conn, CommandEvent, &mut problems).await?;
+    check_entity(conn, ExcludedMatch, &mut problems).await?;
+    check_entity(conn, Hero, &mut problems).await?;
+    check_entity(conn, HeroFacet, &mut problems).await?;
+    check_entity(conn, HeroNickname, &mut problems).await?;
+    check_entity(conn, MatchIngestError, &mut problems).await?;
+    check_entity(conn, MatchPlayerDetail, &mut problems).await?;
+    check_entity(conn, ParseJob, &mut problems).await?;
+    check_entity(conn, Patch, &mut problems).await?;
+    check_entity(conn, Player, &mut problems).await?;
+    check_entity(conn, PlayerMatch, &mut problems).await?;
+    check_entity(conn, PlayerProfile, &mut problems).await?;
+    check_entity(conn, PlayerRule, &mut problems).await?;
+    check_entity(conn, PlayerServer, &mut problems).await?;
+    check_entity(conn, PlayerSyncState, &mut problems).await?;
+    check_entity(conn, RankHistory, &mut problems).await?;
+    check_entity(conn, Server, &mut problems).await?;
+
+    if !problems.is_empty() {
+        return Err(format!(
+            "Database schema does not match the entities:\n- {}",
+            problems.join("\n- ")
+        )
+        .into());
+    }
+
+    info!("Database schema matches entities");
+    Ok(())
+}
+
+async fn check_entity<E: EntityTrait>(
+    conn: &DatabaseConnection,
+    entity: E,
+    problems: &mut Vec<String>,
+) -> Result<(), Error> {
+    let table = entity.table_name();
+    let live = live_columns(conn, table).await?;
+    if live.is_empty() {
+        problems.push(format!("table `{}` is missing", table));
+        return Ok(());
+    }
+
+    for column in E::Column::iter() {
+        let name = column.as_str();
+        let Some(live_column) = live.get(name) else {
+            problems.push(format!("column `{}.{}` is missing", table, name));
+            continue;
+        };
+
+        // INTEGER PRIMARY KEY is the rowid, which is never null even without NOT NULL
+        let live_nullable = !live_column.not_null && !live_column.is_primary_key;
+        if live_nullable != column.def().is_null() {
+            warn!(
+                table,
+                column = name,
+                live_nullable,
+                entity_nullable = column.def().is_null(),
+                "Column nullability differs from the entity"
+            );
+        }
+    }
+
+    let entity_columns: BTreeSet<&str> = E::Column::iter().map(|c| c.as_str()).collect();
+    for (name, live_column) in &live {
+        if !entity_columns.contains(name.as_str())
+            && live_column.not_null
+            && !live_column.has_default
+        {
+            problems.push(format!(
+                "column `{}.{}` is NOT NULL without a default but unknown to the entity",
+                table, name
+            ));
+        }
+    }
+
+    let entity_key: BTreeSet<&str> = E::PrimaryKey::iter()
+        .map(|k| k.into_column().as_str())
+        .collect();
+    let live_key: BTreeSet<&str> = live
+        .iter()
+        .filter(|(_, c)| c.is_primary_key)
+        .map(|(name, _)| name.as_str())
+        .collect();
+    if entity_key != live_key {
+        problems.push(format!(
+            "table `{}` has primary key {:?}, the entity expects {:?}",
+            table, live_key, entity_key
+        ));
+    }
+
+    Ok(())
+}
+
+async fn live_columns(
+    conn: &DatabaseConnection,
+    table: &str,
+) -> Result<HashMap<String, LiveColumn>, Error> {
+    let rows = conn
+        .query_all(Statement::from_string(
+            DbBackend::Sqlite,
+            format!("PRAGMA table_info({})", table),
+        ))
+        .await?;
+
+    let mut columns = HashMap::new();
+    for row in rows {
+        columns.insert(
+            row.try_get::<String>("", "name")?,
+            LiveColumn {
+                not_null: row.try_get::<i32>("", "notnull")? != 0,
+                has_default: row.try_get::<Option<String>>("", "dflt_value")?.is_some(),
+                is_primary_key: row.try_get::<i32>("", "pk")? > 0,
+            },
+        );
+    }
+    Ok(columns)
+}
@@ -1,12 +1,20 @@
 use sea_orm::*;
 use tracing::info;
 
-use crate::database::database_access;
+use crate::database::{database_access, excluded_matches_db};
 use crate::database::entities::{server, Server};
+use crate::database::types::MatchFilter;
 use crate::Error;
 
 pub use server::Model as DiscordServer;
 
+impl DiscordServer {
+    /// Game modes and lobby types only; `query_match_filter` also loads exclusions.
+    pub fn match_filter(&self) -> MatchFilter {
+        MatchFilter::from_columns(self.game_modes.as_deref(), self.lobby_types.as_deref())
+    }
+}
+
 pub async fn query_server_by_id(server_id: i64) -> Result<Option<DiscordServer>, Error> {
     let txn = database_access::get_transaction().await?;
     let server = Server::find_by_id(server_id).one(&txn).await?;
@@ -149,6 +157,50 @@ pub async fn update_server_monthly_hour(server_id: i64, monthly_hour: i32) -> Re
     Ok(())
 }
 
+pub async fn update_server_sub_rank(server_id: i64, is_sub_rank: i32) -> Result<(), Error> {
+    let txn = database_access::get_transaction().await?;
+    let server = Server::find_by_id(server_id).one(&txn).await?;
+
+    if let Some(s) = server {
+        let mut s_active: server::ActiveModel = s.into();
+        s_active.is_sub_rank = Set(is_sub_rank);
+        s_active.update(&txn).await?;
+    }
+
+    txn.commit().await?;
+    Ok(())
+}
+
+/// The server's match filter including excluded matches, or the defaults if the server isn't registered.
+pub async fn query_match_filter(server_id: i64) -> Result<MatchFilter, Error> {
+    let server = query_server_by_id(server_id).await?;
+    let mut filter = match server {
+        Some(s) => s.match_filter(),
+        None => MatchFilter::from_columns(None, None),
+    };
+    filter.excluded_match_ids = excluded_matches_db::query_exclusions_for_server(server_id)
+        .await?
+        .into_iter()
+        .map(|e| e.match_id)
+        .collect();
+    Ok(filter)
+}
+
+pub async fn update_server_match_filter(server_id: i64, filter: &MatchFilter) -> Result<(), Error> {
+    let txn = database_access::get_transaction().await?;
+    let server = Server::find_by_id(server_id).one(&txn).await?;
+
+    if let Some(s) = server {
+        let mut s_active: server::ActiveModel = s.into();
+        s_active.game_modes = Set(Some(MatchFilter::to_column(&filter.game_modes)));
+        s_active.lobby_types = Set(Some(MatchFilter::to_column(&filter.lobby_types)));
+        s_active.update(&txn).await?;
+    }
+
+    txn.commit().await?;
+    Ok(())
+}
+
 pub async fn insert_server(server_id: i64, server_name: &str) -> Result<(), Error> {
     let txn = database_access::get_transaction().await?;
     let existing = Server::find_by_id(server_id).one(&txn).await?;
@@ -163,11 +215,14 @@ pub async fn insert_server(server_id: i64, server_name: &str) -> Result<(), Erro
         is_sub_week: Set(0),
         is_sub_month: Set(0),
         is_sub_reload: Set(0),
+        is_sub_rank: Set(0),
         weekly_day: Set(None),
         weekly_hour: Set(None),
         monthly_week: Set(None),
         monthly_weekday: Set(None),
         monthly_hour: Set(None),
+        game_modes: Set(None),
+        lobby_types: Set(None),
     };
     Server::insert(new_server).exec(&txn).await?;
     txn.commit().await?;
@@ -7,6 +7,19 @@ pub(crate) enum MapperError {
     UnknownHero { hero_id: i32, match_id: i64 },
 }
 
+impl MapperError {
+    /// The variant name, stored with quarantined matches.
+    pub fn kind(&self) -> &'static str {
+        match self {
+            Self::MissingField { .. } => "MissingField",
+            Self::InvalidDuration { .. } => "InvalidDuration",
+            Self::InvalidGameMode { .. } => "InvalidGameMode",
+            Self::InvalidLobbyType { .. } => "InvalidLobbyType",
+            Self::UnknownHero { .. } => "UnknownHero",
+        }
+    }
+}
+
 impl std::fmt::Display for MapperError {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
@@ -40,16 +53,84 @@ pub(crate) enum GameMode {
     RandomDraft = 3,
     SingleDraft = 4,
     AllRandom = 5,
+    Intro = 6,
+    Diretide = 7,
+    ReverseCaptainsMode = 8,
+    Greeviling = 9,
+    Tutorial = 10,
+    MidOnly = 11,
     LeastPlayed = 12,
+    LimitedHeroes = 13,
+    CompendiumMatchmaking = 14,
+    Custom = 15,
     CaptainsDraft = 16,
+    BalancedDraft = 17,
+    AbilityDraft = 18,
+    Event = 19,
+    AllRandomDeathMatch = 20,
+    SoloMid1v1 = 21,
     Ranked = 22,
     Turbo = 23,
+    Mutation = 24,
+    CoachesChallenge = 25,
 }
 
 impl GameMode {
+    /// Offered in the server settings menu. Discord allows at most 25 options, so retired modes
+    /// are still stored but can't be selected.
+    pub const SELECTABLE: [GameMode; 16] = [
+        Self::Unknown,
+        Self::AllPick,
+        Self::CaptainsMode,
+        Self::RandomDraft,
+        Self::SingleDraft,
+        Self::AllRandom,
+        Self::LeastPlayed,
+        Self::Custom,
+        Self::CaptainsDraft,
+        Self::AbilityDraft,
+        Self::Event,
+        Self::AllRandomDeathMatch,
+        Self::SoloMid1v1,
+        Self::Ranked,
+        Self::Turbo,
+        Self::Mutation,
+    ];
+
     pub fn as_i32(self) -> i32 {
         self as i32
     }
+
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::Unknown => "Unknown",
+            Self::AllPick => "All Pick",
+            Self::CaptainsMode => "Captains Mode",
+            Self::RandomDraft => "Random Draft",
+            Self::SingleDraft => "Single Draft",
+            Self::AllRandom => "All Random",
+            Self::Intro => "Intro",
+            Self::Diretide => "Diretide",
+            Self::ReverseCaptainsMode => "Reverse Captains Mode",
+            Self::Greeviling => "Greeviling",
+            Self::Tutorial => "Tutorial",
+            Self::MidOnly => "Mid Only",
+            Self::LeastPlayed => "Least Played",
+            Self::LimitedHeroes => "Limited Heroes",
+            Self::CompendiumMatchmaking => "Compendium Matchmaking",
+            Self::Custom => "Custom",
+            Self::CaptainsDraft => "Captains Draft",
+            Self::BalancedDraft => "Balanced Draft",
+            Self::AbilityDraft => "Ability Draft",
+            Self::Event => "Event",
+            Self::AllRandomDeathMatch => "All Random Deathmatch",
+            Self::SoloMid1v1 => "1v1 Solo Mid",
+            Self::Ranked => "Ranked All Pick",
+            Self::Turbo => "Turbo",
+            Self::Mutation => "Mutation",
+            Self::CoachesChallenge => "Coaches Challenge",
+        }
+    }
 }
 
 impl TryFrom<i32> for GameMode {
@@ -63,10 +144,26 @@ impl TryFrom<i32> for GameMode {
             3 => Self::RandomDraft,
             4 => Self::SingleDraft,
             5 => Self::AllRandom,
+            6 => Self::Intro,
+            7 => Self::Diretide,
+            8 => Self::ReverseCaptainsMode,
+            9 => Self::Greeviling,
+            10 => Self::Tutorial,
+            11 => Self::MidOnly,
             12 => Self::LeastPlayed,
+            13 => Self::LimitedHeroes,
+            14 => Self::CompendiumMatchmaking,
+            15 => Self::Custom,
             16 => Self::CaptainsDraft,
+            17 => Self::BalancedDraft,
+            18 => Self::AbilityDraft,
+            19 => Self::Event,
+            20 => Self::AllRandomDeathMatch,
+            21 => Self::SoloMid1v1,
             22 => Self::Ranked,
             23 => Self::Turbo,
+            24 => Self::Mutation,
+            25 => Self::CoachesChallenge,
             _ => return Err(()),
         })
     }
@@ -88,9 +185,82 @@ pub(crate) enum LobbyType {
 }
 
 impl LobbyType {
+    pub const ALL: [LobbyType; 10] = [
+        Self::Unranked,
+        Self::Practice,
+        Self::Tournament,
+        Self::Tutorial,
+        Self::CoopBots,
+        Self::RankedTeam,
+        Self::RankedSolo,
+        Self::Ranked,
+        Self::SoloMid1v1,
+        Self::BattleCup,
+    ];
+
     pub fn as_i32(self) -> i32 {
         self as i32
     }
+
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::Unranked => "Unranked",
+            Self::Practice => "Practice",
+            Self::Tournament => "Tournament",
+            Self::Tutorial => "Tutorial",
+            Self::CoopBots => "Co-op vs Bots",
+            Self::RankedTeam => "Team Ranked",
+            Self::RankedSolo => "Solo Ranked",
+            Self::Ranked => "Ranked",
+            Self::SoloMid1v1 => "1v1 Solo Mid",
+            Self::BattleCup => "Battle Cup",
+        }
+    }
+}
+
+/// OpenDota `leaver_status` 1 (disconnected) and 2 (disconnected too long) count as an abandon,
+/// the same matches that used to be dropped on ingest.
+pub(crate) fn is_abandon(leaver_status: i32) -> bool {
+    matches!(leaver_status, 1 | 2)
+}
+
+/// Which stored matches a server counts. All valid matches are stored, so this is applied when
+/// querying. Stored on `servers` as comma-separated ids, with `NULL` meaning the defaults.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MatchFilter {
+    pub game_modes: Vec<i32>,
+    pub lobby_types: Vec<i32>,
+    /// Matches excluded by admins, for this server or globally. Not stored on `servers`.
+    pub excluded_match_ids: Vec<i64>,
+}
+
+impl MatchFilter {
+    const DEFAULT_GAME_MODES: [GameMode; 2] = [GameMode::Ranked, GameMode::AllPick];
+    const DEFAULT_LOBBY_TYPES: [LobbyType; 3] =
+        [LobbyType::Unranked, LobbyType::Ranked, LobbyType::RankedSolo];
+
+    pub fn from_columns(game_modes: Option<&str>, lobby_types: Option<&str>) -> Self {
+        Self {
+            game_modes: game_modes.map(parse_ids).unwrap_or_else(|| {
+                Self::DEFAULT_GAME_MODES.iter().map(|m| m.as_i32()).collect()
+            }),
+            lobby_types: lobby_types.map(parse_ids).unwrap_or_else(|| {
+                Self::DEFAULT_LOBBY_TYPES.iter().map(|l| l.as_i32()).collect()
+            }),
+            excluded_match_ids: Vec::new(),
+        }
+    }
+
+    pub fn to_column(ids: &[i32]) -> String {
+        ids.iter().map(|id| id.to_string()).collect::<Vec<_>>().join(",")
+    }
+}
+
+fn parse_ids(value: &str) -> Vec<i32> {
+    value
+        .split(',')
+        .filter_map(|id| id.trim().parse().ok())
+        .collect()
 }
 
 impl TryFrom<i32> for LobbyType {
@@ -0,0 +1,111 @@
+use poise::serenity_prelude as serenity;
+
+use crate::database::heroes_db::HeroLookup;
+use crate::database::{player_matches_db, player_servers_db, servers_db};
+use crate::discord::discord_helper::{self, CmdCtx, Ephemeral};
+use crate::leaderboard::benchmarks::{self, BenchmarkStat};
+use crate::leaderboard::emoji::Emoji;
+use crate::{Context, Error};
+
+/// Compare recent games on a hero against global percentiles
+#[poise::command(slash_command, guild_only)]
+#[tracing::instrument(level = "trace", skip(ctx))]
+pub async fn benchmarks(
+    ctx: Context<'_>,
+    #[description = "Hero name or nickname (e.g. Storm Spirit or stormspirit)"] hero_name: String,
+    #[description = "Whose games to compare, defaults to you"] user: Option<serenity::User>,
+) -> Result<(), Error> {
+    let cmd_ctx = discord_helper::get_command_ctx(ctx).await?;
+    let discord_user_id = user.as_ref().unwrap_or_else(|| ctx.author()).id.get() as i64;
+    benchmarks_command(&cmd_ctx, &hero_name, discord_user_id).await
+}
+
+async fn benchmarks_command(
+    ctx: &CmdCtx<'_>,
+    hero_name: &str,
+    discord_user_id: i64,
+) -> Result<(), Error> {
+    let hero_lookup = HeroLookup::load().await?;
+    let Some(hero) = hero_lookup.find_by_name(hero_name) else {
+        ctx.reply(
+            Ephemeral::Private,
+            format!("{} Hero **{}** not found.", Emoji::SILENCE, hero_name),
+        )
+        .await?;
+        return Ok(());
+    };
+
+    let player =
+        player_servers_db::query_player_by_discord_user(ctx.guild_id, discord_user_id).await?;
+    let Some(player) = player else {
+        ctx.reply(
+            Ephemeral::Private,
+            "That user is not linked to a player on this server.",
+        )
+        .await?;
+        return Ok(());
+    };
+    let display_name = player
+        .player_name
+        .clone()
+        .unwrap_or_else(|| player.discord_name.clone());
+
+    let match_filter = servers_db::query_match_filter(ctx.guild_id).await?;
+    let matches =
+        player_matches_db::query_matches_by_hero(player.player_id, hero.hero_id, &match_filter)
+            .await?;
+    let stats = benchmarks::hero_benchmarks(hero.hero_id, player.player_id, &matches).await?;
+    if stats.is_empty() {
+        ctx.reply(
+            Ephemeral::Private,
+            format!(
+                "{} No benchmark data for **{}** on **{}**.",
+                Emoji::SILENCE,
+                display_name,
+                hero.name
+            ),
+        )
+        .await?;
+        return Ok(());
+    }
+
+    let content = format!(
+        "## {} **{}** on **{}** - last {} games vs everyone\n{}",
+        Emoji::MIDAS,
+        display_name,
+        hero.name,
+        matches.len().min(benchmarks::RECENT_GAMES),
+        format_stats(&stats)
+    );
+    ctx.reply(Ephemeral::Public, content).await?;
+    Ok(())
+}
+
+fn format_stats(stats: &[BenchmarkStat]) -> String {
+    stats
+        .iter()
+        .map(|s| {
+            format!(
+                "- {}: **{}** - {} ({} games)",
+                s.label,
+                s.average,
+                ordinal_percentile(s.percentile),
+                s.games
+            )
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// e.g. `72nd percentile`.
+fn ordinal_percentile(percentile: f64) -> String {
+    let n = percentile as i64;
+    let suffix = match (n % 10, n % 100) {
+        (_, 11..=13) => "th",
+        (1, _) => "st",
+        (2, _) => "nd",
+        (3, _) => "rd",
+        _ => "th",
+    };
+    format!("{n}{suffix} percentile")
+}
@@ -0,0 +1,80 @@
+use crate::api::api_wrapper::{self, HeroMatchup};
+use crate::database::heroes_db::{HeroLookup, Position};
+use crate::discord::discord_helper::{self, CmdCtx, Ephemeral};
+use crate::leaderboard::emoji::Emoji;
+use crate::{Context, Error};
+
+const MATCHUPS_PER_LIST: usize = 5;
+
+/// Show the heroes that counter a hero, and the ones it beats
+#[poise::command(slash_command, guild_only)]
+#[tracing::instrument(level = "trace", skip(ctx))]
+pub async fn counter(
+    ctx: Context<'_>,
+    #[description = "Hero name or nickname (e.g. Storm Spirit or stormspirit)"] hero_name: String,
+    #[description = "Only suggest heroes played in this position"] position: Option<Position>,
+) -> Result<(), Error> {
+    let cmd_ctx = discord_helper::get_command_ctx(ctx).await?;
+    counter_command(&cmd_ctx, &hero_name, position).await
+}
+
+async fn counter_command(
+    ctx: &CmdCtx<'_>,
+    hero_name: &str,
+    position: Option<Position>,
+) -> Result<(), Error> {
+    let hero_lookup = HeroLookup::load().await?;
+    let Some(hero) = hero_lookup.find_by_name(hero_name) else {
+        ctx.reply(
+            Ephemeral::Private,
+            format!("{} Hero **{}** not found.", Emoji::SILENCE, hero_name),
+        )
+        .await?;
+        return Ok(());
+    };
+
+    let matchups = api_wrapper::hero_matchups(hero.hero_id, position, MATCHUPS_PER_LIST).await?;
+    if matchups.worst.is_empty() {
+        ctx.reply(
+            Ephemeral::Private,
+            format!("{} Not enough matchup data for **{}**.", Emoji::SILENCE, hero.name),
+        )
+        .await?;
+        return Ok(());
+    }
+
+    let position_label = match position {
+        Some(position) => format!(" ({:?})", position),
+        None => String::new(),
+    };
+    let content = format!(
+        "## {} Counters to **{}**{}\n{}\n## {} **{}** does well against{}\n{}",
+        Emoji::DUEL,
+        hero.name,
+        position_label,
+        format_matchups(&matchups.worst),
+        Emoji::COOL,
+        hero.name,
+        position_label,
+        format_matchups(&matchups.best),
+    );
+    ctx.reply(Ephemeral::Public, content).await?;
+    Ok(())
+}
+
+fn format_matchups(matchups: &[HeroMatchup]) -> String {
+    matchups
+        .iter()
+        .enumerate()
+        .map(|(i, m)| {
+            format!(
+                "{}. **{}** - {:.1}% win rate over {} games",
+                i + 1,
+                m.hero.name,
+                m.win_rate_pct,
+                m.games
+            )
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
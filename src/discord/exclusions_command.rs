@@ -0,0 +1,111 @@
+use crate::api::open_dota_links;
+use crate::database::excluded_matches_db;
+use crate::discord::discord_helper::{self, CmdCtx, Ephemeral};
+use crate::util::dates;
+use crate::{Context, Error};
+
+const MAX_LISTED_EXCLUSIONS: usize = 15;
+
+/// [Admin] Exclude a match from this server's leaderboards and stats
+#[poise::command(slash_command, guild_only)]
+#[tracing::instrument(level = "trace", skip(ctx))]
+pub async fn exclude_match(
+    ctx: Context<'_>,
+    #[description = "The match to exclude"] match_id: i64,
+    #[description = "Why the match is excluded"] reason: String,
+) -> Result<(), Error> {
+    let cmd_ctx = discord_helper::get_command_ctx(ctx).await?;
+    exclude_match_command(&cmd_ctx, match_id, &reason).await
+}
+
+/// [Admin] Restore a match previously excluded on this server
+#[poise::command(slash_command, guild_only)]
+#[tracing::instrument(level = "trace", skip(ctx))]
+pub async fn restore_match(
+    ctx: Context<'_>,
+    #[description = "The match to restore"] match_id: i64,
+) -> Result<(), Error> {
+    let cmd_ctx = discord_helper::get_command_ctx(ctx).await?;
+    restore_match_command(&cmd_ctx, match_id).await
+}
+
+/// [Admin] List matches excluded on this server
+#[poise::command(slash_command, guild_only)]
+#[tracing::instrument(level = "trace", skip(ctx))]
+pub async fn excluded_matches(ctx: Context<'_>) -> Result<(), Error> {
+    let cmd_ctx = discord_helper::get_command_ctx(ctx).await?;
+    excluded_matches_command(&cmd_ctx).await
+}
+
+async fn exclude_match_command(ctx: &CmdCtx<'_>, match_id: i64, reason: &str) -> Result<(), Error> {
+    let excluded_by = ctx.discord_ctx.author().id.get() as i64;
+    let inserted = excluded_matches_db::insert_exclusion(
+        match_id,
+        Some(ctx.guild_id),
+        reason,
+        Some(excluded_by),
+    )
+    .await?;
+
+    let message = if inserted {
+        format!(
+            "Excluded match [{}]({}) from this server's leaderboards and stats.",
+            match_id,
+            open_dota_links::match_url(match_id)
+        )
+    } else {
+        format!("Match {} is already excluded on this server.", match_id)
+    };
+    ctx.reply(Ephemeral::Private, message).await?;
+    Ok(())
+}
+
+async fn restore_match_command(ctx: &CmdCtx<'_>, match_id: i64) -> Result<(), Error> {
+    let deleted = excluded_matches_db::delete_exclusion(match_id, Some(ctx.guild_id)).await?;
+
+    let message = if deleted {
+        format!("Restored match {} on this server.", match_id)
+    } else {
+        format!(
+            "Match {} is not excluded on this server. Global exclusions can only be lifted by the bot operator.",
+            match_id
+        )
+    };
+    ctx.reply(Ephemeral::Private, message).await?;
+    Ok(())
+}
+
+async fn excluded_matches_command(ctx: &CmdCtx<'_>) -> Result<(), Error> {
+    let exclusions = excluded_matches_db::query_exclusions_for_server(ctx.guild_id).await?;
+    if exclusions.is_empty() {
+        ctx.reply(Ephemeral::Private, "No matches are excluded on this server.")
+            .await?;
+        return Ok(());
+    }
+
+    let mut content = format!("## Excluded Matches\n> **Total:** {}\n", exclusions.len());
+    for exclusion in exclusions.iter().take(MAX_LISTED_EXCLUSIONS) {
+        let scope = match (exclusion.server_id, exclusion.excluded_by) {
+            (None, _) => "all servers".to_string(),
+            (Some(_), Some(user_id)) => format!("by <@{}>", user_id),
+            (Some(_), None) => "this server".to_string(),
+        };
+        content.push_str(&format!(
+            "- [{}]({}) {} ({}, {})\n",
+            exclusion.match_id,
+            open_dota_links::match_url(exclusion.match_id),
+            exclusion.reason,
+            scope,
+            dates::discord_relative_from_timestamp(exclusion.excluded_at)
+        ));
+    }
+    if exclusions.len() > MAX_LISTED_EXCLUSIONS {
+        content.push_str(&format!(
+            "- ...and {} more\n",
+            exclusions.len() - MAX_LISTED_EXCLUSIONS
+        ));
+    }
+
+    ctx.reply(Ephemeral::Private, content).await?;
+    Ok(())
+}
@@ -0,0 +1,78 @@
+use crate::api::{api_wrapper, open_dota_links};
+use crate::database::match_ingest_errors_db::{self, MatchIngestErrorModel};
+use crate::database::player_servers_db;
+use crate::discord::discord_helper::{self, CmdCtx, Ephemeral};
+use crate::util::dates;
+use crate::{Context, Error};
+
+const MAX_LISTED_ERRORS: usize = 10;
+
+/// [Admin] List matches that failed to import for this server's players
+#[poise::command(slash_command, guild_only)]
+#[tracing::instrument(level = "trace", skip(ctx))]
+pub async fn ingest_errors(ctx: Context<'_>) -> Result<(), Error> {
+    let cmd_ctx = discord_helper::get_command_ctx(ctx).await?;
+    ingest_errors_command(&cmd_ctx).await
+}
+
+/// [Admin] Retry importing matches that previously failed for this server's players
+#[poise::command(slash_command, guild_only)]
+#[tracing::instrument(level = "trace", skip(ctx))]
+pub async fn retry_ingest_errors(ctx: Context<'_>) -> Result<(), Error> {
+    let cmd_ctx = discord_helper::get_command_ctx(ctx).await?;
+    retry_ingest_errors_command(&cmd_ctx).await
+}
+
+async fn server_player_ids(ctx: &CmdCtx<'_>) -> Result<Vec<i64>, Error> {
+    let players = player_servers_db::query_server_players(ctx.guild_id).await?;
+    Ok(players.into_iter().map(|p| p.player_id).collect())
+}
+
+async fn ingest_errors_command(ctx: &CmdCtx<'_>) -> Result<(), Error> {
+    let player_ids = server_player_ids(ctx).await?;
+    let errors = match_ingest_errors_db::query_errors_by_player_ids(player_ids).await?;
+    if errors.is_empty() {
+        ctx.reply(Ephemeral::Private, "No match ingest errors for this server's players.")
+            .await?;
+        return Ok(());
+    }
+
+    let mut content = format!("## Match Ingest Errors\n> **Total:** {}\n", errors.len());
+    append_errors(&mut content, &errors);
+    content.push_str("\nUse `/retry_ingest_errors` once the cause is fixed.");
+
+    ctx.reply(Ephemeral::Private, content).await?;
+    Ok(())
+}
+
+async fn retry_ingest_errors_command(ctx: &CmdCtx<'_>) -> Result<(), Error> {
+    let player_ids = server_player_ids(ctx).await?;
+    let stat = api_wrapper::retry_ingest_errors(player_ids).await?;
+
+    ctx.reply(
+        Ephemeral::Private,
+        format!(
+            "Imported {} matches, {} still failing.",
+            stat.imported, stat.still_failing
+        ),
+    )
+    .await?;
+    Ok(())
+}
+
+fn append_errors(content: &mut String, errors: &[MatchIngestErrorModel]) {
+    for error in errors.iter().take(MAX_LISTED_ERRORS) {
+        content.push_str(&format!(
+            "- [{}]({}) player {} **{}**: {} (seen {})\n",
+            error.match_id,
+            open_dota_links::match_url(error.match_id),
+            error.player_id,
+            error.error_kind,
+            error.error_message,
+            dates::discord_relative_from_timestamp(error.updated_at),
+        ));
+    }
+    if errors.len() > MAX_LISTED_ERRORS {
+        content.push_str(&format!("- ...and {} more\n", errors.len() - MAX_LISTED_ERRORS));
+    }
+}
@@ -2,7 +2,7 @@ use chrono::Utc;
 use poise::CreateReply;
 use tracing::{error, info};
 
-use crate::database::player_servers_db;
+use crate::database::{patches_db, player_servers_db};
 use crate::discord::discord_helper::{self, CmdCtx, Ephemeral};
 use crate::leaderboard::duration::Duration;
 use crate::leaderboard::leaderboard_stats::get_leaderboard_messages;
@@ -21,8 +21,18 @@ pub async fn leaderboard(
 }
 
 pub async fn leaderboard_command(ctx: &CmdCtx<'_>, duration: Duration) -> Result<(), Error> {
+    if matches!(duration, Duration::ThisPatch) && patches_db::query_current_patch().await?.is_none()
+    {
+        ctx.reply(
+            Ephemeral::Private,
+            "The patch list hasn't been synced yet, so This Patch isn't available. Try again after the next hero sync.",
+        )
+        .await?;
+        return Ok(());
+    }
+
     let end_utc = Utc::now();
-    let start_utc = duration.start_date(end_utc);
+    let start_utc = duration.start_date(end_utc).await?;
     let players = player_servers_db::query_server_players(ctx.guild_id).await?;
     if players.is_empty() {
         error!(
@@ -52,7 +62,15 @@ pub async fn leaderboard_command(ctx: &CmdCtx<'_>, duration: Duration) -> Result
         )
         .await?;
 
-    let messages = get_leaderboard_messages(ctx.guild_id, players, &start_utc, &end_utc, &duration_label).await?;
+    let messages = get_leaderboard_messages(
+        ctx.guild_id,
+        players,
+        &start_utc,
+        &end_utc,
+        &duration_label,
+        &ctx.app_cfg.leaderboard,
+    )
+    .await?;
 
     if messages.is_empty() {
         let content = format!(
@@ -8,6 +8,7 @@ pub struct MentionHandler {
     pub max_recent_match_days: u64,
     pub max_recent_matches: usize,
     pub top_winrate_count: usize,
+    pub count_abandons_in_win_rates: bool,
 }
 
 #[async_trait]
@@ -147,6 +148,8 @@ impl MentionHandler {
             max_recent_match_days: self.max_recent_match_days,
             max_recent_matches: self.max_recent_matches,
             top_winrate_count: self.top_winrate_count,
+            count_abandons_in_win_rates: self.count_abandons_in_win_rates,
+            requester_discord_id: message.author.id.get() as i64,
         }
     }
 
@@ -2,10 +2,15 @@ use poise::serenity_prelude::Permissions;
 
 use crate::{Data, Error};
 
+mod benchmarks_command;
+mod counter_command;
 mod discord_helper;
+mod exclusions_command;
+mod ingest_errors_command;
 pub(crate) mod mention_handler;
 pub(crate) mod leaderboard_command;
 mod misc_commands;
+mod parse_jobs_command;
 mod register_command;
 mod reload_command;
 mod heroes_command;
@@ -19,11 +24,19 @@ pub(crate) async fn commands() -> Vec<poise::Command<crate::Data, crate::Error>>
         leaderboard_command::leaderboard(),
         register_command::register_to_leaderboard(),
         heroes_command::heroes(),
+        counter_command::counter(),
+        benchmarks_command::benchmarks(),
     ];
 
     let admin_cmds: Vec<poise::Command<Data, Error>> = vec![
         server_settings_command::admin_panel(),
         reload_command::refresh_server_matches(),
+        parse_jobs_command::parse_jobs(),
+        ingest_errors_command::ingest_errors(),
+        ingest_errors_command::retry_ingest_errors(),
+        exclusions_command::exclude_match(),
+        exclusions_command::restore_match(),
+        exclusions_command::excluded_matches(),
     ];
 
     for mut admin_cmd in admin_cmds.into_iter() {
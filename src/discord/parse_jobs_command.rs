@@ -0,0 +1,74 @@
+use crate::api::open_dota_links;
+use crate::database::parse_jobs_db::{self, ParseJobModel, ParseJobState};
+use crate::discord::discord_helper::{self, CmdCtx, Ephemeral};
+use crate::util::dates;
+use crate::{Context, Error};
+
+const MAX_LISTED_JOBS: usize = 10;
+
+/// [Admin] Show pending and failed replay parse jobs for this server's players
+#[poise::command(slash_command, guild_only)]
+#[tracing::instrument(level = "trace", skip(ctx))]
+pub async fn parse_jobs(ctx: Context<'_>) -> Result<(), Error> {
+    let cmd_ctx = discord_helper::get_command_ctx(ctx).await?;
+    parse_jobs_command(&cmd_ctx).await
+}
+
+async fn parse_jobs_command(ctx: &CmdCtx<'_>) -> Result<(), Error> {
+    let jobs = parse_jobs_db::query_jobs_for_server(ctx.guild_id).await?;
+    if jobs.is_empty() {
+        ctx.reply(Ephemeral::Private, "No parse jobs for this server's players.")
+            .await?;
+        return Ok(());
+    }
+
+    let by_state = |state: ParseJobState| -> Vec<&ParseJobModel> {
+        jobs.iter().filter(|j| j.state == state.as_str()).collect()
+    };
+    let queued = by_state(ParseJobState::Queued);
+    let requested = by_state(ParseJobState::Requested);
+    let parsed = by_state(ParseJobState::Parsed);
+    let failed = by_state(ParseJobState::Failed);
+
+    let mut content = format!(
+        "## Parse Jobs\n> **Queued:** {} | **Requested:** {} | **Parsed:** {} | **Failed:** {}\n",
+        queued.len(),
+        requested.len(),
+        parsed.len(),
+        failed.len()
+    );
+
+    let pending: Vec<&ParseJobModel> = queued.into_iter().chain(requested).collect();
+    append_jobs(&mut content, "Pending", &pending);
+    append_jobs(&mut content, "Failed", &failed);
+
+    ctx.reply(Ephemeral::Private, content).await?;
+    Ok(())
+}
+
+fn append_jobs(content: &mut String, title: &str, jobs: &[&ParseJobModel]) {
+    if jobs.is_empty() {
+        return;
+    }
+
+    content.push_str(&format!("\n**{title}**\n"));
+    for job in jobs.iter().take(MAX_LISTED_JOBS) {
+        let error = job
+            .last_error
+            .as_ref()
+            .map(|e| format!(" - {e}"))
+            .unwrap_or_default();
+        content.push_str(&format!(
+            "- [{}]({}) {} ({} attempts, updated {}){}\n",
+            job.match_id,
+            open_dota_links::match_url(job.match_id),
+            job.state,
+            job.attempts,
+            dates::discord_relative_from_timestamp(job.updated_at),
+            error
+        ));
+    }
+    if jobs.len() > MAX_LISTED_JOBS {
+        content.push_str(&format!("- ...and {} more\n", jobs.len() - MAX_LISTED_JOBS));
+    }
+}
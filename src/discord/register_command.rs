@@ -1,3 +1,6 @@
+use tracing::warn;
+
+use crate::api::{api_cache, match_source};
 use crate::database::{player_servers_db, players_db};
 use crate::discord::discord_helper::{get_command_ctx, Ephemeral};
 use crate::{Context, Error};
@@ -49,6 +52,27 @@ pub async fn register_to_leaderboard(
         return Ok(());
     }
 
+    // The profile check is best-effort, so registering still works while OpenDota is down.
+    // Fixtures only record some players, so a missing one proves nothing there.
+    let source = match_source::source_for_server(cmd_ctx.guild_id);
+    match api_cache::get_player(source, dota_player_id).await {
+        Ok(api_player) if api_player.profile.is_none() && source.name() != "fixture" => {
+            cmd_ctx
+                .reply(
+                    Ephemeral::Private,
+                    format!("No OpenDota profile found for Dota player ID {dota_player_id}. Check the ID and try again."),
+                )
+                .await?;
+            return Ok(());
+        }
+        Ok(_) => {}
+        Err(e) => warn!(
+            dota_player_id,
+            error = %e,
+            "Could not verify player profile, registering without the check"
+        ),
+    }
+
     let discord_name = discord_user
         .global_name
         .as_ref()
@@ -1,7 +1,7 @@
 use chrono::Utc;
 use poise::ReplyHandle;
 
-use crate::api::api_wrapper;
+use crate::api::api_wrapper::{self, ParallelReload, SyncMode};
 use crate::database::{command_events_db, player_servers_db};
 use crate::discord::discord_helper::{self, CmdCtx, Ephemeral};
 use crate::util::dates;
@@ -19,9 +19,18 @@ pub async fn refresh_matches(ctx: Context<'_>) -> Result<(), Error> {
 /// [Admin] Refresh all matches for all players on the server
 #[poise::command(slash_command, prefix_command)]
 #[tracing::instrument(level = "trace", skip(ctx))]
-pub async fn refresh_server_matches(ctx: Context<'_>) -> Result<(), Error> {
+pub async fn refresh_server_matches(
+    ctx: Context<'_>,
+    #[description = "Re-download every player's full match history instead of only new matches"]
+    full_resync: Option<bool>,
+) -> Result<(), Error> {
     let cmd_ctx = discord_helper::get_command_ctx(ctx).await?;
-    refresh_server_matches_command(&cmd_ctx).await?;
+    let mode = if full_resync.unwrap_or(false) {
+        SyncMode::Full
+    } else {
+        SyncMode::Incremental
+    };
+    refresh_server_matches_command(&cmd_ctx, mode).await?;
     Ok(())
 }
 
@@ -72,7 +81,7 @@ async fn refresh_user_matches_command(ctx: &CmdCtx<'_>) -> Result<(), Error> {
         )
         .await?;
 
-    let stat = api_wrapper::reload_player(&player).await;
+    let stat = api_wrapper::reload_player(&player, SyncMode::Incremental).await;
 
     let message = match stat.result {
         Ok(Some(count)) => format!("Refreshed {} matches for {}", count, stat.display_name),
@@ -96,7 +105,7 @@ async fn refresh_user_matches_command(ctx: &CmdCtx<'_>) -> Result<(), Error> {
     Ok(())
 }
 
-async fn refresh_server_matches_command(ctx: &CmdCtx<'_>) -> Result<(), Error> {
+async fn refresh_server_matches_command(ctx: &CmdCtx<'_>, mode: SyncMode) -> Result<(), Error> {
     let author = ctx.discord_ctx.author();
     let discord_user_id = author.id.get() as i64;
 
@@ -129,48 +138,40 @@ async fn refresh_server_matches_command(ctx: &CmdCtx<'_>) -> Result<(), Error> {
         return Ok(());
     }
 
-    let reply = ctx
-        .reply(
-            Ephemeral::Public,
-            format!(
-                "Refreshing player matches for {} players. Message will be edited with progress updates.\n",
-                players.len()
+    let header = format!(
+        "Refreshing player matches for {} players{}. Message will be edited with progress updates.\n",
+        players.len(),
+        if mode == SyncMode::Full { " (full resync)" } else { "" }
+    );
+    let reply = ctx.reply(Ephemeral::Public, header.clone()).await?;
+
+    // Lines are kept in player order, so the message reads the same however the reloads interleave.
+    let total = players.len();
+    let mut lines: Vec<Option<String>> = vec![None; total];
+    let mut completed = 0;
+    let mut reloads =
+        ParallelReload::spawn(players, mode, ctx.app_cfg.opendota.reload_concurrency);
+
+    while let Some((index, stat)) = reloads.next().await {
+        let line = match stat.result {
+            Ok(Some(count)) => format!("Refreshed {} matches for {}", count, stat.display_name),
+            Ok(None) => format!(
+                "No dota matches found for {} with PlayerId={}. Removing player from cache.",
+                stat.display_name, stat.player_id
             ),
-        )
-        .await?;
-
-    for player in &players {
-        let stat = api_wrapper::reload_player(player).await;
-        match stat.result {
-            Ok(Some(count)) => {
-                add_to_reply(
-                    ctx,
-                    &reply,
-                    &format!("Refreshed {} matches for {}\n", count, stat.display_name),
-                )
-                .await?;
-            }
-            Ok(None) => {
-                add_to_reply(
-                    ctx,
-                    &reply,
-                    &format!(
-                        "No dota matches found for {} with PlayerId={}. Removing player from cache.\n",
-                        stat.display_name,
-                        stat.player_id
-                    ),
-                )
-                .await?;
-            }
-            Err(e) => {
-                add_to_reply(
-                    ctx,
-                    &reply,
-                    &format!("Failed to refresh {} : {}\n", stat.display_name, e),
-                )
-                .await?;
-            }
-        }
+            Err(e) => format!("Failed to refresh {} : {}", stat.display_name, e),
+        };
+        lines[index] = Some(line);
+        completed += 1;
+
+        let progress = format!(
+            "{}\n**Progress:** {}/{}\n{}",
+            header,
+            completed,
+            total,
+            lines.iter().flatten().cloned().collect::<Vec<_>>().join("\n")
+        );
+        update_reply(ctx, &reply, progress).await;
     }
 
     command_events_db::insert_event(
@@ -205,20 +206,9 @@ async fn check_cooldown(
     Ok(None)
 }
 
-async fn add_to_reply(
-    ctx: &CmdCtx<'_>,
-    reply: &ReplyHandle<'_>,
-    append_text: &str,
-) -> Result<(), Error> {
-    let message = reply.message().await?;
-    let new_content = format!("{}\n{}", message.content, append_text);
+async fn update_reply(ctx: &CmdCtx<'_>, reply: &ReplyHandle<'_>, content: String) {
     reply
-        .edit(
-            ctx.discord_ctx,
-            poise::CreateReply::default().content(new_content),
-        )
+        .edit(ctx.discord_ctx, poise::CreateReply::default().content(content))
         .await
         .ok();
-    Ok(())
 }
-
@@ -11,6 +11,7 @@ use serenity::all::{
 use tracing::info;
 
 use crate::database::player_servers_db::PlayerServerModel;
+use crate::database::types::{GameMode, LobbyType, MatchFilter};
 use crate::database::{database_access, player_rules_db, player_servers_db, servers_db};
 use crate::discord::discord_helper::{self, CmdCtx};
 use crate::leaderboard::emoji::Emoji;
@@ -19,6 +20,7 @@ use crate::{Context, Error};
 const BUTTON_ID_WEEK: &str = "dotacord_admin_week";
 const BUTTON_ID_MONTH: &str = "dotacord_admin_month";
 const BUTTON_ID_RELOAD: &str = "dotacord_admin_reload";
+const BUTTON_ID_RANK: &str = "dotacord_admin_rank";
 
 const SELECT_ID_CHANNEL: &str = "dotacord_admin_channel";
 const SELECT_ID_WEEKLY_DAY: &str = "dotacord_admin_weekly_day";
@@ -26,10 +28,13 @@ const SELECT_ID_WEEKLY_HOUR: &str = "dotacord_admin_weekly_hour";
 const SELECT_ID_MONTHLY_WEEK: &str = "dotacord_admin_monthly_week";
 const SELECT_ID_MONTHLY_WEEKDAY: &str = "dotacord_admin_monthly_weekday";
 const SELECT_ID_MONTHLY_HOUR: &str = "dotacord_admin_monthly_hour";
+const SELECT_ID_GAME_MODES: &str = "dotacord_admin_game_modes";
+const SELECT_ID_LOBBY_TYPES: &str = "dotacord_admin_lobby_types";
 
 const BUTTON_ID_CONFIG_WEEKLY: &str = "dotacord_config_weekly";
 const BUTTON_ID_CONFIG_MONTHLY: &str = "dotacord_config_monthly";
 const BUTTON_ID_PLAYERS: &str = "dotacord_config_players";
+const BUTTON_ID_MATCH_FILTER: &str = "dotacord_config_match_filter";
 const BUTTON_ID_BACK: &str = "dotacord_back";
 
 const SELECT_ID_PLAYER: &str = "dotacord_player_select";
@@ -52,6 +57,7 @@ enum Panel {
     Monthly,
     Players,
     Rules,
+    MatchFilter,
 }
 
 struct ServerState {
@@ -59,11 +65,13 @@ struct ServerState {
     is_sub_week: i32,
     is_sub_month: i32,
     is_sub_reload: i32,
+    is_sub_rank: i32,
     weekly_day: Option<i32>,
     weekly_hour: Option<i32>,
     monthly_week: Option<i32>,
     monthly_weekday: Option<i32>,
     monthly_hour: Option<i32>,
+    match_filter: MatchFilter,
     selected_discord_user: Option<(i64, String)>,
     players: Vec<PlayerServerModel>,
     rules: Vec<player_rules_db::PlayerRuleModel>,
@@ -93,11 +101,13 @@ async fn generate_admin_panel(ctx: &CmdCtx<'_>) -> Result<(), Error> {
         is_sub_week: server.is_sub_week,
         is_sub_month: server.is_sub_month,
         is_sub_reload: server.is_sub_reload,
+        is_sub_rank: server.is_sub_rank,
         weekly_day: server.weekly_day,
         weekly_hour: server.weekly_hour,
         monthly_week: server.monthly_week,
         monthly_weekday: server.monthly_weekday,
         monthly_hour: server.monthly_hour,
+        match_filter: server.match_filter(),
         selected_discord_user: None,
         players,
         rules: Vec::new(),
@@ -148,6 +158,12 @@ async fn generate_admin_panel(ctx: &CmdCtx<'_>) -> Result<(), Error> {
                 let status = if state.is_sub_reload != 0 { "enabled" } else { "disabled" };
                 info!(server_id = ctx.guild_id, status, "Auto-reload subscription updated");
             }
+            BUTTON_ID_RANK => {
+                state.is_sub_rank = 1 - state.is_sub_rank;
+                servers_db::update_server_sub_rank(ctx.guild_id, state.is_sub_rank).await?;
+                let status = if state.is_sub_rank != 0 { "enabled" } else { "disabled" };
+                info!(server_id = ctx.guild_id, status, "Rank announcement subscription updated");
+            }
             BUTTON_ID_CONFIG_WEEKLY => {
                 current_panel = Panel::Weekly;
             }
@@ -170,6 +186,9 @@ async fn generate_admin_panel(ctx: &CmdCtx<'_>) -> Result<(), Error> {
             BUTTON_ID_PLAYERS => {
                 current_panel = Panel::Players;
             }
+            BUTTON_ID_MATCH_FILTER => {
+                current_panel = Panel::MatchFilter;
+            }
             BUTTON_ID_RULES => {
                 if let Some((discord_user_id, _)) = state.selected_discord_user {
                     state.rules = player_rules_db::query_rules_by_player(
@@ -246,6 +265,23 @@ async fn generate_admin_panel(ctx: &CmdCtx<'_>) -> Result<(), Error> {
                     }
                 }
             }
+            SELECT_ID_GAME_MODES | SELECT_ID_LOBBY_TYPES => {
+                if let ComponentInteractionDataKind::StringSelect { values } = &interaction.data.kind {
+                    let ids: Vec<i32> = values.iter().filter_map(|v| v.parse().ok()).collect();
+                    if custom_id == SELECT_ID_GAME_MODES {
+                        state.match_filter.game_modes = ids;
+                    } else {
+                        state.match_filter.lobby_types = ids;
+                    }
+                    servers_db::update_server_match_filter(ctx.guild_id, &state.match_filter).await?;
+                    info!(
+                        server_id = ctx.guild_id,
+                        game_modes = ?state.match_filter.game_modes,
+                        lobby_types = ?state.match_filter.lobby_types,
+                        "Match filter updated"
+                    );
+                }
+            }
             SELECT_ID_PLAYER => {
                 if let ComponentInteractionDataKind::UserSelect { values } = &interaction.data.kind {
                     if let Some(user_id) = values.first() {
@@ -592,6 +628,7 @@ fn build_panel(panel: Panel, state: &ServerState) -> (String, Vec<CreateComponen
         Panel::Monthly => build_monthly_panel(state),
         Panel::Players => build_players_panel(state),
         Panel::Rules => build_rules_panel(state),
+        Panel::MatchFilter => build_match_filter_panel(state),
     }
 }
 
@@ -614,12 +651,18 @@ fn build_main_panel(state: &ServerState) -> (String, Vec<CreateComponent<'static
     if let Some(emoji) = discord_helper::parse_custom_emoji(Emoji::MEEP_MOP) {
         players_btn = players_btn.emoji(emoji);
     }
-    let players_row = CreateActionRow::Buttons(vec![players_btn].into());
+    let match_filter_btn = CreateButton::new(BUTTON_ID_MATCH_FILTER)
+        .style(ButtonStyle::Primary)
+        .label("Counted Game Modes".to_string());
+    let players_row = CreateActionRow::Buttons(vec![players_btn, match_filter_btn].into());
 
     let components = vec![
         CreateComponent::ActionRow(CreateActionRow::SelectMenu(build_channel_select(state.channel_id))),
         CreateComponent::ActionRow(config_row),
-        CreateComponent::ActionRow(CreateActionRow::Buttons(vec![build_toggle_button(BUTTON_ID_RELOAD, "Auto Reload Toggle", state.is_sub_reload)].into())),
+        CreateComponent::ActionRow(CreateActionRow::Buttons(vec![
+            build_toggle_button(BUTTON_ID_RELOAD, "Auto Reload Toggle", state.is_sub_reload),
+            build_toggle_button(BUTTON_ID_RANK, "Rank Announcements Toggle", state.is_sub_rank),
+        ].into())),
         CreateComponent::ActionRow(players_row),
     ];
 
@@ -682,6 +725,55 @@ fn build_monthly_panel(state: &ServerState) -> (String, Vec<CreateComponent<'sta
     (content, components)
 }
 
+fn build_match_filter_panel(state: &ServerState) -> (String, Vec<CreateComponent<'static>>) {
+    let content = format!(
+        "## {} **Counted Game Modes** {}\n> Which game modes and lobby types count towards leaderboards and stats?\n> Every match is stored, so changes apply to past matches too.",
+        Emoji::GUILD, Emoji::AEGIS2015
+    );
+
+    let game_mode_options: Vec<CreateSelectMenuOption> = GameMode::SELECTABLE
+        .iter()
+        .map(|mode| {
+            CreateSelectMenuOption::new(mode.label(), mode.as_i32().to_string())
+                .default_selection(state.match_filter.game_modes.contains(&mode.as_i32()))
+        })
+        .collect();
+    let game_mode_select = CreateSelectMenu::new(
+        SELECT_ID_GAME_MODES.to_string(),
+        CreateSelectMenuKind::String {
+            options: game_mode_options.into(),
+        },
+    )
+    .placeholder("Select game modes".to_string())
+    .min_values(1)
+    .max_values(GameMode::SELECTABLE.len() as u8);
+
+    let lobby_type_options: Vec<CreateSelectMenuOption> = LobbyType::ALL
+        .iter()
+        .map(|lobby| {
+            CreateSelectMenuOption::new(lobby.label(), lobby.as_i32().to_string())
+                .default_selection(state.match_filter.lobby_types.contains(&lobby.as_i32()))
+        })
+        .collect();
+    let lobby_type_select = CreateSelectMenu::new(
+        SELECT_ID_LOBBY_TYPES.to_string(),
+        CreateSelectMenuKind::String {
+            options: lobby_type_options.into(),
+        },
+    )
+    .placeholder("Select lobby types".to_string())
+    .min_values(1)
+    .max_values(LobbyType::ALL.len() as u8);
+
+    let components = vec![
+        CreateComponent::ActionRow(CreateActionRow::SelectMenu(game_mode_select)),
+        CreateComponent::ActionRow(CreateActionRow::SelectMenu(lobby_type_select)),
+        CreateComponent::ActionRow(build_back_button_row()),
+    ];
+
+    (content, components)
+}
+
 fn build_toggle_button(custom_id: &str, label: &str, is_enabled: i32) -> CreateButton<'static> {
     let (emoji_str, style, status) = if is_enabled != 0 {
         (Emoji::GOODJOB, ButtonStyle::Success, "Enabled")
@@ -0,0 +1,149 @@
+use std::collections::HashMap;
+
+use crate::api::api_cache;
+use crate::api::open_dota_api::ApiBenchmarkPoint;
+use crate::database::match_details_db::{self, MatchPlayerDetailModel};
+use crate::database::player_matches_db::PlayerMatchModel;
+use crate::Error;
+
+/// How many of the player's most recent games on the hero are compared.
+pub const RECENT_GAMES: usize = 20;
+
+/// A benchmark stat, keyed like OpenDota's `/benchmarks` result.
+struct Metric {
+    key: &'static str,
+    label: &'static str,
+    /// The value for one game, or `None` when the game has no data for it.
+    value: fn(&PlayerMatchModel, Option<&MatchPlayerDetailModel>) -> Option<f64>,
+}
+
+const METRICS: [Metric; 7] = [
+    Metric {
+        key: "gold_per_min",
+        label: "GPM",
+        value: |_, d| Some(d?.gpm? as f64),
+    },
+    Metric {
+        key: "xp_per_min",
+        label: "XPM",
+        value: |_, d| Some(d?.xpm? as f64),
+    },
+    Metric {
+        key: "kills_per_min",
+        label: "Kills/min",
+        value: |m, _| per_min(m, Some(m.kills)),
+    },
+    Metric {
+        key: "last_hits_per_min",
+        label: "Last hits/min",
+        value: |m, d| per_min(m, d?.last_hits),
+    },
+    Metric {
+        key: "hero_damage_per_min",
+        label: "Hero damage/min",
+        value: |m, d| per_min(m, d?.hero_damage),
+    },
+    Metric {
+        key: "hero_healing_per_min",
+        label: "Healing/min",
+        value: |m, d| per_min(m, d?.hero_healing),
+    },
+    Metric {
+        key: "tower_damage",
+        label: "Tower damage",
+        value: |_, d| Some(d?.tower_damage? as f64),
+    },
+];
+
+fn per_min(player_match: &PlayerMatchModel, total: Option<i32>) -> Option<f64> {
+    let minutes = player_match.duration as f64 / 60.0;
+    if minutes <= 0.0 {
+        return None;
+    }
+    total.map(|t| t as f64 / minutes)
+}
+
+#[derive(Debug, Clone)]
+pub struct BenchmarkStat {
+    pub label: &'static str,
+    /// The player's average across the games that had data for this stat.
+    pub average: f64,
+    /// 0 to 100, where the player sits among all games on the hero.
+    pub percentile: f64,
+    pub games: usize,
+}
+
+/// Compares the most recent `RECENT_GAMES` of `matches` (newest first, all on `hero_id`) against
+/// OpenDota's benchmarks. Stats without data, e.g. GPM before match details are fetched, are left out.
+pub async fn hero_benchmarks(
+    hero_id: i32,
+    player_id: i64,
+    matches: &[PlayerMatchModel],
+) -> Result<Vec<BenchmarkStat>, Error> {
+    let recent = &matches[..matches.len().min(RECENT_GAMES)];
+    if recent.is_empty() {
+        return Ok(vec![]);
+    }
+
+    let benchmarks = api_cache::get_hero_benchmarks(hero_id).await?;
+    let match_ids = recent.iter().map(|m| m.match_id).collect();
+    let details: HashMap<i64, MatchPlayerDetailModel> =
+        match_details_db::query_details(player_id, match_ids)
+            .await?
+            .into_iter()
+            .map(|d| (d.match_id, d))
+            .collect();
+
+    let stats = METRICS
+        .iter()
+        .filter_map(|metric| {
+            let curve = benchmarks.result.get(metric.key)?;
+            let values: Vec<f64> = recent
+                .iter()
+                .filter_map(|m| (metric.value)(m, details.get(&m.match_id)))
+                .collect();
+            if values.is_empty() {
+                return None;
+            }
+
+            let average = values.iter().sum::<f64>() / values.len() as f64;
+            Some(BenchmarkStat {
+                label: metric.label,
+                average: (average * 100.0).round() / 100.0,
+                percentile: percentile_of(curve, average)?,
+                games: values.len(),
+            })
+        })
+        .collect();
+
+    Ok(stats)
+}
+
+/// Linear interpolation along the curve. Values below the first point scale down from 0,
+/// values above the last point get the last point's percentile.
+fn percentile_of(curve: &[ApiBenchmarkPoint], value: f64) -> Option<f64> {
+    let first = curve.first()?;
+    let fraction = if value <= first.value {
+        if first.value > 0.0 {
+            first.percentile * (value / first.value).max(0.0)
+        } else {
+            first.percentile
+        }
+    } else {
+        curve
+            .windows(2)
+            .find(|w| value <= w[1].value)
+            .map(|w| {
+                let span = w[1].value - w[0].value;
+                if span > 0.0 {
+                    let progress = (value - w[0].value) / span;
+                    w[0].percentile + (w[1].percentile - w[0].percentile) * progress
+                } else {
+                    w[1].percentile
+                }
+            })
+            .unwrap_or_else(|| curve[curve.len() - 1].percentile)
+    };
+
+    Some((fraction * 100.0).round())
+}
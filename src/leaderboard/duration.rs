@@ -1,6 +1,9 @@
 use chrono::{DateTime, Datelike, NaiveDate, TimeZone, Utc};
 use poise::ChoiceParameter;
 
+use crate::database::patches_db;
+use crate::Error;
+
 #[derive(Debug, Clone, Copy, ChoiceParameter)]
 pub enum Duration {
     Day,
@@ -9,11 +12,14 @@ pub enum Duration {
     Year,
     #[name = "All Time"]
     AllTime,
+    #[name = "This Patch"]
+    ThisPatch,
 }
 
 impl Duration {
-    pub fn start_date(self, end: DateTime<Utc>) -> DateTime<Utc> {
-        match self {
+    /// `ThisPatch` starts at the latest patch release, so it needs the synced patch list.
+    pub async fn start_date(self, end: DateTime<Utc>) -> Result<DateTime<Utc>, Error> {
+        Ok(match self {
             Duration::Day => end - chrono::Duration::days(1),
             Duration::Week => end - chrono::Duration::weeks(1),
             Duration::Month => subtract_months(end, 1),
@@ -22,7 +28,15 @@ impl Duration {
                 .with_ymd_and_hms(2010, 1, 1, 0, 0, 0)
                 .single()
                 .expect("valid all-time anchor"),
-        }
+            Duration::ThisPatch => {
+                let patch = patches_db::query_current_patch().await?.ok_or_else(|| {
+                    Error::from("No patches known yet, the patch list syncs with the hero catalog")
+                })?;
+                DateTime::from_timestamp(patch.release_time, 0).ok_or_else(|| {
+                    Error::from(format!("Invalid release time for patch {}", patch.name))
+                })?
+            }
+        })
     }
 
     /// Human-friendly short label for the duration (used in leaderboard headings).
@@ -33,6 +47,7 @@ impl Duration {
             Duration::Month => "Month",
             Duration::Year => "Year",
             Duration::AllTime => "All Time",
+            Duration::ThisPatch => "This Patch",
         }
     }
 }
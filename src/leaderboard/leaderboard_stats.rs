@@ -1,7 +1,13 @@
 use chrono::{DateTime, Utc};
 use tracing::info;
 
-use crate::database::{command_events_db, heroes_db, player_matches_db, player_servers_db};
+use crate::api::open_dota_client;
+use crate::config::LeaderboardConfig;
+use crate::database::types::MatchFilter;
+use crate::database::{
+    command_events_db, heroes_db, player_matches_db, player_profiles_db, player_servers_db,
+    servers_db,
+};
 use crate::leaderboard::emoji::Emoji;
 use crate::leaderboard::section::LeaderboardSection;
 use crate::leaderboard::stats_calculator::{self, PlayerStats};
@@ -15,10 +21,20 @@ pub async fn get_leaderboard_messages(
     start_utc: &DateTime<Utc>,
     end_utc: &DateTime<Utc>,
     duration_label: &str,
+    config: &LeaderboardConfig,
 ) -> Result<Vec<String>, Error> {
-    let all_stats = leaderboard_stats::get_player_stats(players, &start_utc, &end_utc).await?;
+    let match_filter = servers_db::query_match_filter(server_id).await?;
+    let all_stats = leaderboard_stats::get_player_stats(
+        players,
+        &start_utc,
+        &end_utc,
+        &match_filter,
+        config.count_abandons_in_win_rates,
+    )
+    .await?;
     let hero_lookup = heroes_db::HeroLookup::load().await?;
-    let sections = sections::get_leaderboard_sections(&duration_label, &all_stats, &hero_lookup);
+    let sections =
+        sections::get_leaderboard_sections(&duration_label, &all_stats, &hero_lookup, config);
 
     let last_reload = command_events_db::query_last_event(server_id, command_events_db::EventType::AdminRefresh, None).await?;
     let last_refreshed = match last_reload {
@@ -36,7 +52,7 @@ pub async fn get_leaderboard_messages(
         return Ok(vec![]);
     }
 
-    let title = format!(
+    let mut title = format!(
         "# {} {}ly Leaderboard {} - {} -> {}\n> Last refreshed: {}\n",
         Emoji::TOP1,
         duration_label,
@@ -46,6 +62,14 @@ pub async fn get_leaderboard_messages(
         last_refreshed
     );
 
+    if let Some(since) = open_dota_client::outage_since() {
+        title.push_str(&format!(
+            "> {} OpenDota is unavailable, data may be stale since {}\n",
+            Emoji::OPEN_DOTA,
+            dates::discord_relative_from_timestamp(since)
+        ));
+    }
+
     let mut messages = vec![title];
     messages.extend(section_messages);
     Ok(messages)
@@ -55,13 +79,19 @@ async fn get_player_stats(
     players: Vec<player_servers_db::PlayerServerModel>,
     start_utc: &DateTime<Utc>,
     end_utc: &DateTime<Utc>,
+    match_filter: &MatchFilter,
+    count_abandons_in_win_rates: bool,
 ) -> Result<Vec<PlayerStats>, Error> {
+    let player_ids = players.iter().map(|p| p.player_id).collect();
+    let profiles = player_profiles_db::query_profiles_by_player_ids(player_ids).await?;
+
     let mut all_stats = Vec::new();
     for player in players {
         let matches = player_matches_db::query_matches_by_duration(
             player.player_id,
             start_utc.timestamp() as i32,
             end_utc.timestamp() as i32,
+            match_filter,
         )
         .await?;
 
@@ -80,6 +110,8 @@ async fn get_player_stats(
                 .player_name
                 .clone()
                 .unwrap_or_else(|| player.discord_name.clone()),
+            profiles.get(&player.player_id).and_then(|p| p.rank_tier),
+            count_abandons_in_win_rates,
         )?;
         all_stats.push(stats);
     }
@@ -0,0 +1,41 @@
+use crate::leaderboard::emoji::Emoji;
+
+pub const MEDAL_NAMES: [&str; 8] = [
+    "Herald", "Guardian", "Crusader", "Archon", "Legend", "Ancient", "Divine", "Immortal",
+];
+
+/// Splits an OpenDota `rank_tier` (e.g. 53 = Legend 3) into medal (1-8) and stars (0-5).
+fn split_rank_tier(rank_tier: Option<i32>) -> Option<(i32, i32)> {
+    let tier = rank_tier?;
+    let medal = tier / 10;
+    (1..=8).contains(&medal).then_some((medal, tier % 10))
+}
+
+/// The medal (1 = Herald to 8 = Immortal) for an OpenDota `rank_tier`.
+pub fn medal_of(rank_tier: Option<i32>) -> Option<i32> {
+    split_rank_tier(rank_tier).map(|(medal, _)| medal)
+}
+
+/// Only some medals have a server emoji, so each medal maps to the closest one available.
+pub fn medal_emoji(rank_tier: Option<i32>) -> &'static str {
+    match split_rank_tier(rank_tier) {
+        Some((1 | 2, _)) => Emoji::HERALD,
+        Some((3 | 4, _)) => Emoji::ARCHON,
+        Some((5 | 6, _)) => Emoji::ANCIENT,
+        Some((7 | 8, _)) => Emoji::IMMORTAL,
+        _ => Emoji::UNCALIBRATED,
+    }
+}
+
+/// e.g. `Legend 3`, `Immortal #512` or `Uncalibrated`.
+pub fn medal_name(rank_tier: Option<i32>, leaderboard_rank: Option<i32>) -> String {
+    match split_rank_tier(rank_tier) {
+        Some((8, _)) => match leaderboard_rank {
+            Some(rank) => format!("Immortal #{rank}"),
+            None => "Immortal".to_string(),
+        },
+        Some((medal, stars)) if stars > 0 => format!("{} {stars}", MEDAL_NAMES[medal as usize - 1]),
+        Some((medal, _)) => MEDAL_NAMES[medal as usize - 1].to_string(),
+        None => "Uncalibrated".to_string(),
+    }
+}
@@ -1,6 +1,8 @@
+pub mod benchmarks;
 pub mod duration;
 pub mod emoji;
 pub mod leaderboard_stats;
+pub mod medal;
 pub mod section;
 pub(crate) mod section_formatter;
 pub(crate) mod sections;
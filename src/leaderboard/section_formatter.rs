@@ -2,7 +2,8 @@ use super::stats_calculator::PlayerStats;
 use crate::api::open_dota_links;
 use crate::database::heroes_db::HeroLookup;
 use crate::leaderboard::section::LeaderboardSection;
-use crate::markdown::{Link, TableBuilder, Text};
+use crate::leaderboard::medal;
+use crate::markdown::{Icon, Link, TableBuilder, Text};
 use crate::str;
 use crate::util::dates::format_short_date_from_timestamp;
 
@@ -47,6 +48,7 @@ pub fn build_winrate_section(
     }
     Some(
         builder
+            .add_column(medal_column(&sorted_stats))
             .add_column(Text::new(
                 "Player",
                 sorted_stats.iter().map(|s| str!(s.player_name)).collect(),
@@ -136,6 +138,7 @@ pub fn build_hero_spam_section(
     }
     Some(
         builder
+            .add_column(medal_column(&sorted_stats))
             .add_column(Text::new(
                 "Player",
                 sorted_stats.iter().map(|s| str!(s.player_name)).collect(),
@@ -272,6 +275,7 @@ pub fn build_single_match_stat_section(
         builder = builder.add_column(Link::new(link_urls));
     }
     builder = builder
+        .add_column(medal_column(&sorted_stats))
         .add_column(Text::new("Player", player_column))
         .add_column(Text::new(stat_name, stat_column))
         .add_column(Text::new("Hero", hero_column))
@@ -283,6 +287,74 @@ pub fn build_single_match_stat_section(
     Some(section)
 }
 
+pub fn build_abandons_section(
+    duration_label: &str,
+    all_stats: &[PlayerStats],
+    left_emoji: &str,
+    right_emoji: &str,
+    title_text: &str,
+    include_links: bool,
+) -> Option<LeaderboardSection> {
+    let mut sorted_stats: Vec<_> = all_stats.iter().filter(|s| s.abandons > 0).collect();
+
+    sorted_stats.sort_by(|a, b| {
+        b.abandons
+            .cmp(&a.abandons)
+            .then_with(|| b.most_recent_match_time.cmp(&a.most_recent_match_time))
+    });
+
+    let winner = sorted_stats.first()?;
+    let title = format!(
+        "[{duration_label}] - {left_emoji} {title_text} {right_emoji} - __*{}*__ - `{} Abandons`",
+        winner.player_name, winner.abandons
+    );
+
+    let mut builder = TableBuilder::new(title);
+    if include_links {
+        let link_urls: Vec<String> = sorted_stats
+            .iter()
+            .map(|s| open_dota_links::profile_url(s.player_id))
+            .collect();
+        builder = builder.add_column(Link::new(link_urls));
+    }
+    Some(
+        builder
+            .add_column(medal_column(&sorted_stats))
+            .add_column(Text::new(
+                "Player",
+                sorted_stats.iter().map(|s| str!(s.player_name)).collect(),
+            ))
+            .add_column(Text::new(
+                "Abandons",
+                sorted_stats.iter().map(|s| str!(s.abandons)).collect(),
+            ))
+            .add_column(Text::new(
+                "Played",
+                sorted_stats.iter().map(|s| str!(s.matches_played)).collect(),
+            ))
+            .add_column(Text::new(
+                "Rate",
+                sorted_stats
+                    .iter()
+                    .map(|s| {
+                        let rate = (s.abandons as f64 / s.matches_played as f64) * 100.0;
+                        format!("{:>3.0}%", rate)
+                    })
+                    .collect(),
+            ))
+            .build(),
+    )
+}
+
+fn medal_column(sorted_stats: &[&PlayerStats]) -> Icon {
+    Icon::new(
+        sorted_stats
+            .iter()
+            .map(|s| str!(medal::medal_emoji(s.rank_tier)))
+            .collect(),
+    )
+}
+
 fn format_duration(seconds: i32) -> String {
     let hours = seconds / 3600;
     let minutes = (seconds % 3600) / 60;
@@ -375,6 +447,7 @@ pub fn build_longest_match_section(
         builder = builder.add_column(Link::new(link_urls));
     }
     builder = builder
+        .add_column(medal_column(&sorted_stats))
         .add_column(Text::new("Player", player_column))
         .add_column(Text::new("Duration", duration_column))
         .add_column(Text::new("Hero", hero_column))
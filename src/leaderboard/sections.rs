@@ -1,3 +1,4 @@
+use crate::config::LeaderboardConfig;
 use crate::database::heroes_db::HeroLookup;
 use crate::leaderboard::section::LeaderboardSection;
 
@@ -9,8 +10,9 @@ pub(crate) fn get_leaderboard_sections(
     duration_label: &str,
     all_stats: &[PlayerStats],
     hero_lookup: &HeroLookup,
+    config: &LeaderboardConfig,
 ) -> Vec<Option<LeaderboardSection>> {
-    vec![
+    let mut sections = vec![
         format_overall_win_rate_section(duration_label, &all_stats, false),
         format_ranked_win_rate_section(duration_label, &all_stats, false),
         format_hero_spam_section(duration_label, &all_stats, false, hero_lookup),
@@ -18,7 +20,13 @@ pub(crate) fn get_leaderboard_sections(
         format_highest_assists_section(duration_label, &all_stats, true, hero_lookup),
         format_highest_deaths_section(duration_label, &all_stats, true, hero_lookup),
         format_longest_match_section(duration_label, &all_stats, true, hero_lookup),
-    ]
+    ];
+
+    if config.abandons_section {
+        sections.push(format_abandons_section(duration_label, &all_stats, true));
+    }
+
+    sections
 }
 
 fn format_overall_win_rate_section(
@@ -146,3 +154,18 @@ fn format_longest_match_section(
         hero_lookup,
     )
 }
+
+fn format_abandons_section(
+    duration_label: &str,
+    all_stats: &[PlayerStats],
+    include_links: bool,
+) -> Option<LeaderboardSection> {
+    section_formatter::build_abandons_section(
+        duration_label,
+        all_stats,
+        Emoji::THROWGAME,
+        Emoji::GRAVE,
+        "Rage Quitter",
+        include_links,
+    )
+}
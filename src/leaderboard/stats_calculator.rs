@@ -1,11 +1,12 @@
 use crate::database::player_matches_db::PlayerMatchModel;
-use crate::database::types::LobbyType;
+use crate::database::types::{self, LobbyType};
 use crate::Error;
 
 #[derive(Debug, Clone, Default)]
 pub struct PlayerStats {
     pub player_id: i64,
     pub player_name: String,
+    pub rank_tier: Option<i32>,
 
     pub overall_stats: OverallStats,
     pub ranked_stats: OverallStats,
@@ -17,6 +18,10 @@ pub struct PlayerStats {
     pub longest_match_stat: SingleMatchStat,
 
     pub most_recent_match_time: i64,
+
+    /// Every match in the period, including abandons left out of the win rates.
+    pub matches_played: i32,
+    pub abandons: i32,
 }
 
 #[derive(Debug, Clone, Default)]
@@ -99,6 +104,8 @@ pub fn player_matches_to_stats(
     matches: &[PlayerMatchModel],
     player_id: i64,
     player_name: String,
+    rank_tier: Option<i32>,
+    count_abandons_in_win_rates: bool,
 ) -> Result<PlayerStats, Error> {
     let mut overall_stats = OverallStats::new();
     let mut ranked_stats = OverallStats::new();
@@ -111,22 +118,31 @@ pub fn player_matches_to_stats(
     let mut highest_deaths_tracker = SingleMatchTracker::new();
     let mut longest_match_tracker = SingleMatchTracker::new();
 
-    for player_match in matches {
-        // Overall Stats
-        overall_stats.track(player_match);
+    let mut abandons = 0;
 
-        if player_match.lobby_type == LobbyType::Ranked.as_i32()
-            || player_match.lobby_type == LobbyType::RankedSolo.as_i32()
-        {
-            ranked_stats.track(player_match);
+    for player_match in matches {
+        let is_abandon = types::is_abandon(player_match.leaver_status);
+        if is_abandon {
+            abandons += 1;
         }
 
-        // Most Played Hero
-        let hero_tracker = hero_trackers
-            .entry(player_match.hero_id)
-            .or_insert_with(|| HeroPickStats::new(player_match.hero_id));
+        if !is_abandon || count_abandons_in_win_rates {
+            // Overall Stats
+            overall_stats.track(player_match);
+
+            if player_match.lobby_type == LobbyType::Ranked.as_i32()
+                || player_match.lobby_type == LobbyType::RankedSolo.as_i32()
+            {
+                ranked_stats.track(player_match);
+            }
 
-        hero_tracker.stats.track(player_match);
+            // Most Played Hero
+            let hero_tracker = hero_trackers
+                .entry(player_match.hero_id)
+                .or_insert_with(|| HeroPickStats::new(player_match.hero_id));
+
+            hero_tracker.stats.track(player_match);
+        }
 
         // Single Match Stats
         highest_kills_tracker.track(player_match, player_match.kills);
@@ -135,11 +151,11 @@ pub fn player_matches_to_stats(
         longest_match_tracker.track(player_match, player_match.duration);
     }
 
+    // Empty when every match was an abandon; the hero spam section skips zero-match entries.
     let hero_pick_stat = hero_trackers
-        .into_iter()
-        .max_by_key(|(_, t)| t.stats.total_matches)
-        .ok_or_else(|| Error::from("No matches found for player when checking most played hero"))?
-        .1;
+        .into_values()
+        .max_by_key(|t| t.stats.total_matches)
+        .unwrap_or_default();
 
     let total_matches = matches.len() as f32;
     let most_recent_match_time = matches.iter().map(|m| m.start_time).max().unwrap_or(0);
@@ -147,6 +163,7 @@ pub fn player_matches_to_stats(
     Ok(PlayerStats {
         player_id,
         player_name,
+        rank_tier,
 
         overall_stats,
         ranked_stats,
@@ -158,6 +175,9 @@ pub fn player_matches_to_stats(
         longest_match_stat: create_single_match_stat(longest_match_tracker, &total_matches)?,
 
         most_recent_match_time,
+
+        matches_played: matches.len() as i32,
+        abandons,
     })
 }
 
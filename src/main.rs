@@ -8,10 +8,12 @@ mod logging;
 mod markdown;
 mod scheduler;
 mod util;
+use std::path::PathBuf;
+
 use ::serenity::all::Token;
 use clap::{Parser, Subcommand};
 use poise::serenity_prelude::{self as serenity};
-use tracing::info;
+use tracing::{info, warn};
 
 #[derive(Parser)]
 #[command(name = "dotacord")]
@@ -31,9 +33,24 @@ enum Command {
         server_id: u64,
         server_name: String,
     },
+    /// Exclude a match for every server
+    ExcludeMatch {
+        match_id: i64,
+        reason: String,
+    },
+    /// Lift a global match exclusion
+    RestoreMatch {
+        match_id: i64,
+    },
+    /// Snapshot the database into the backup directory and prune old snapshots
+    Backup,
+    /// Replace the database with a snapshot. Stop the bot first
+    Restore {
+        snapshot: PathBuf,
+    },
 }
 
-use crate::database::{database_access, servers_db};
+use crate::database::{backup, database_access, excluded_matches_db, servers_db};
 
 #[derive(Debug)]
 struct Data {
@@ -59,15 +76,53 @@ async fn main() -> Result<(), Error> {
     logging::init(&cfg)?;
     info!("Logging Initialised. Initialising Dotacord application");
 
+    // Restore swaps the database file, so it runs before anything opens it
+    if let Some(Command::Restore { snapshot }) = &args.command {
+        backup::restore_snapshot(snapshot, &cfg.database_path).await?;
+        return Ok(());
+    }
+
     database_access::init_database(&cfg.database_path).await?;
     ai::init_client(&cfg.anthropic)?;
-
-    if let Some(Command::RegisterServer { server_id, server_name }) = args.command {
-        if server_name.trim().is_empty() {
-            return Err(Error::from("server_name cannot be empty"));
+    api::open_dota_client::init_client(&cfg.opendota)?;
+    api::match_source::init_source(&cfg.match_source)?;
+
+    match args.command {
+        Some(Command::RegisterServer { server_id, server_name }) => {
+            if server_name.trim().is_empty() {
+                return Err(Error::from("server_name cannot be empty"));
+            }
+            servers_db::insert_server(server_id as i64, &server_name).await?;
+            return Ok(());
         }
-        servers_db::insert_server(server_id as i64, &server_name).await?;
-        return Ok(());
+        Some(Command::ExcludeMatch { match_id, reason }) => {
+            if excluded_matches_db::insert_exclusion(match_id, None, &reason, None).await? {
+                info!(match_id, "Excluded match for all servers");
+            } else {
+                info!(match_id, "Match is already excluded for all servers");
+            }
+            return Ok(());
+        }
+        Some(Command::RestoreMatch { match_id }) => {
+            if excluded_matches_db::delete_exclusion(match_id, None).await? {
+                // Excluded matches are skipped on import, so it may need a full resync to show up.
+                info!(match_id, "Restored match for all servers");
+            } else {
+                info!(match_id, "Match was not excluded for all servers");
+            }
+            return Ok(());
+        }
+        Some(Command::Backup) => {
+            let path = backup::create_backup(&cfg.backup).await?;
+            info!(path = %path.display(), "Database backup completed");
+            return Ok(());
+        }
+        Some(Command::Restore { .. }) | None => {}
+    }
+
+    // A stale catalog only fails reloads containing new heroes, so startup carries on.
+    if let Err(e) = api::api_wrapper::sync_hero_catalog().await {
+        warn!(error = %e, "Failed to sync hero catalog at startup");
     }
 
     let cfg_for_scheduler = cfg.clone();
@@ -121,6 +176,7 @@ async fn main() -> Result<(), Error> {
     let max_recent_match_days = cfg.anthropic.max_recent_match_days.unwrap_or(7);
     let max_recent_matches = cfg.anthropic.max_recent_matches.unwrap_or(20);
     let top_winrate_count = cfg.anthropic.top_winrate_count.unwrap_or(10);
+    let count_abandons_in_win_rates = cfg.leaderboard.count_abandons_in_win_rates;
     let cfg_arc = std::sync::Arc::new(Data { config: cfg });
     let intents =
         serenity::GatewayIntents::non_privileged() | serenity::GatewayIntents::MESSAGE_CONTENT;
@@ -133,6 +189,7 @@ async fn main() -> Result<(), Error> {
             max_recent_match_days,
             max_recent_matches,
             top_winrate_count,
+            count_abandons_in_win_rates,
         }))
         .await?;
 
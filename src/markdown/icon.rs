@@ -0,0 +1,35 @@
+pub struct Icon {
+    values: Vec<String>,
+}
+
+impl Icon {
+    pub fn new(values: Vec<String>) -> Self {
+        Self { values }
+    }
+}
+
+impl crate::markdown::Column for Icon {
+    fn len(&self) -> usize {
+        self.values.len()
+    }
+
+    fn calculate_width(&self) -> usize {
+        // Discord renders custom emoji <:name:id> as a single image, so the markup
+        // length says nothing about the rendered width. Cells are emitted unpadded.
+        0
+    }
+
+    fn format_header(&self, _width: usize) -> String {
+        String::new()
+    }
+
+    fn format_cell(&self, row_index: usize, _width: usize) -> String {
+        self.values[row_index].clone()
+    }
+}
+
+impl From<Icon> for Box<dyn crate::markdown::Column> {
+    fn from(i: Icon) -> Self {
+        Box::new(i)
+    }
+}
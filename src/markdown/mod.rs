@@ -1,9 +1,11 @@
 pub mod column;
+pub mod icon;
 pub mod link;
 pub mod table;
 pub mod text;
 
 pub use column::Column;
+pub use icon::Icon;
 pub use link::Link;
 pub use table::TableBuilder;
 pub use text::Text;
@@ -0,0 +1,16 @@
+use tracing::info;
+
+use crate::database::backup;
+use crate::scheduler::SchedulerContext;
+use crate::{seq_span, Error};
+
+#[tracing::instrument(level = "info", skip(ctx))]
+pub async fn run_backup(ctx: &SchedulerContext) -> Result<(), Error> {
+    let span = seq_span!("backup");
+    let _enter = span.enter();
+
+    let path = backup::create_backup(&ctx.config.backup).await?;
+    info!(path = %path.display(), "Scheduled database backup completed");
+
+    Ok(())
+}
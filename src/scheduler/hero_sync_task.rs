@@ -0,0 +1,18 @@
+use tracing::info;
+
+use crate::api::api_wrapper;
+use crate::scheduler::SchedulerContext;
+use crate::{seq_span, Error};
+
+#[tracing::instrument(level = "info", skip(_ctx))]
+pub async fn sync_heroes(_ctx: &SchedulerContext) -> Result<(), Error> {
+    let span = seq_span!("hero_sync");
+    let _enter = span.enter();
+
+    let added = api_wrapper::sync_hero_catalog().await?;
+    if added > 0 {
+        info!(added, "Scheduled hero catalog sync added heroes");
+    }
+
+    Ok(())
+}
@@ -2,6 +2,7 @@ use chrono::Utc;
 use poise::serenity_prelude as serenity;
 use tracing::{error, info, warn};
 
+use crate::config::LeaderboardConfig;
 use crate::database::{player_servers_db, servers_db};
 use crate::leaderboard::duration::Duration;
 use crate::leaderboard::leaderboard_stats;
@@ -41,7 +42,7 @@ pub async fn publish_leaderboard(
         return Ok(());
     }
 
-    let messages = generate_leaderboard_messages(players, duration, &server).await?;
+    let messages = generate_leaderboard_messages(players, duration, &server, &ctx.config.leaderboard).await?;
 
     if messages.is_empty() {
         info!(
@@ -88,23 +89,31 @@ async fn generate_leaderboard_messages(
     players: Vec<player_servers_db::PlayerServerModel>,
     duration: Duration,
     server: &servers_db::DiscordServer,
+    config: &LeaderboardConfig,
 ) -> Result<Vec<String>, Error> {
     let end_utc = Utc::now();
-    let start_utc = duration.start_date(end_utc);
+    let start_utc = duration.start_date(end_utc).await?;
     let duration_label = duration.to_label();
 
-    leaderboard_stats::get_leaderboard_messages(server.server_id, players, &start_utc, &end_utc, &duration_label)
-        .await
-        .map_err(|e| {
-            error!(
-                server_id = server.server_id,
-                server_name = ?server.server_name,
-                duration = ?duration,
-                error = ?e,
-                "Failed to generate leaderboard"
-            );
-            e
-        })
+    leaderboard_stats::get_leaderboard_messages(
+        server.server_id,
+        players,
+        &start_utc,
+        &end_utc,
+        &duration_label,
+        config,
+    )
+    .await
+    .map_err(|e| {
+        error!(
+            server_id = server.server_id,
+            server_name = ?server.server_name,
+            duration = ?duration,
+            error = ?e,
+            "Failed to generate leaderboard"
+        );
+        e
+    })
 }
 
 async fn send_leaderboard_messages(
@@ -152,7 +161,7 @@ async fn send_leaderboard_messages(
     Ok(())
 }
 
-fn batch_contents(contents: Vec<String>, max_length: usize) -> Vec<String> {
+pub(super) fn batch_contents(contents: Vec<String>, max_length: usize) -> Vec<String> {
     let mut batches = Vec::new();
     let mut current_batch = String::new();
 
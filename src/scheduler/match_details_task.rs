@@ -0,0 +1,21 @@
+use tracing::info;
+
+use crate::api::api_wrapper;
+use crate::scheduler::SchedulerContext;
+use crate::{seq_span, Error};
+
+/// Fills in `/matches/{id}` details for one batch of stored matches. Runs once per scheduler tick,
+/// so the batch size bounds how much of the OpenDota rate limit it takes from reloads.
+#[tracing::instrument(level = "info", skip(ctx))]
+pub async fn ingest_batch(ctx: &SchedulerContext) -> Result<(), Error> {
+    let span = seq_span!("match_details");
+    let _enter = span.enter();
+
+    let batch_size = ctx.config.scheduler.match_details.batch_size;
+    let processed = api_wrapper::ingest_match_details(batch_size).await?;
+    if processed > 0 {
+        info!(processed, batch_size, "Completed match details batch");
+    }
+
+    Ok(())
+}
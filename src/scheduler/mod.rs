@@ -1,4 +1,9 @@
+mod backup_task;
+mod hero_sync_task;
 mod leaderboard_task;
+mod match_details_task;
+mod parse_jobs_task;
+mod rank_task;
 mod reload_task;
 
 use std::sync::Arc;
@@ -9,6 +14,7 @@ use poise::serenity_prelude as serenity;
 use tokio::time;
 use tracing::{debug, error, info};
 
+use crate::api::open_dota_client;
 use crate::database::servers_db;
 use crate::leaderboard::duration::Duration as LeaderboardDuration;
 use crate::{config::AppConfig, Error};
@@ -48,88 +54,150 @@ fn start_schedule_task(ctx: Arc<SchedulerContext>) {
 async fn check_all_tasks(ctx: &SchedulerContext) -> Result<(), Error> {
     debug!("Checking scheduled tasks for all subscribed servers");
 
+    // Tasks that call OpenDota sit out while it's down; this also sends the recovery probe.
+    let open_dota_available = open_dota_client::check_available().await;
+
     let servers = servers_db::query_all_servers().await?;
-    for server in servers {
-        if let Err(e) = check_server_tasks(ctx, &server).await {
+    let due: Vec<DueTasks> = servers
+        .iter()
+        .map(|server| DueTasks {
+            server,
+            weekly: server.is_sub_week == 1 && is_weekly_leaderboard_due(ctx, server),
+            monthly: server.is_sub_month == 1 && is_monthly_leaderboard_due(ctx, server),
+        })
+        .collect();
+
+    // Leaderboards reload first so they include the latest matches.
+    let reload_due = is_reload_due(ctx);
+    let reload_servers: Vec<&servers_db::DiscordServer> = due
+        .iter()
+        .filter(|d| d.weekly || d.monthly || (d.server.is_sub_reload == 1 && reload_due))
+        .map(|d| d.server)
+        .collect();
+    if !reload_servers.is_empty() {
+        if open_dota_available {
+            if let Err(e) = reload_task::auto_reload(ctx, &reload_servers).await {
+                error!(error = ?e, "Scheduled reload failed");
+            }
+        } else {
+            // Leaderboards still publish from stored matches, with a stale-data notice.
+            info!(servers = reload_servers.len(), "OpenDota unavailable, skipping auto-reload");
+        }
+    }
+
+    for tasks in &due {
+        if let Err(e) = publish_due_leaderboards(ctx, tasks).await {
             error!(
                 error = ?e,
-                server_id = server.server_id,
-                server_name = ?server.server_name,
+                server_id = tasks.server.server_id,
+                server_name = ?tasks.server.server_name,
                 "Failed to check tasks for server"
             );
         }
     }
 
+    if ctx.config.scheduler.backup.enabled && is_backup_due(ctx) {
+        if let Err(e) = backup_task::run_backup(ctx).await {
+            error!(error = ?e, "Scheduled database backup failed");
+        }
+    }
+
+    if !open_dota_available {
+        return Ok(());
+    }
+
+    if ctx.config.scheduler.match_details.enabled {
+        if let Err(e) = match_details_task::ingest_batch(ctx).await {
+            error!(error = ?e, "Failed to ingest match details");
+        }
+    }
+
+    if ctx.config.scheduler.hero_sync.enabled && is_hero_sync_due(ctx) {
+        if let Err(e) = hero_sync_task::sync_heroes(ctx).await {
+            error!(error = ?e, "Failed to sync hero catalog");
+        }
+    }
+
+    if ctx.config.scheduler.parse_jobs.enabled {
+        if let Err(e) = parse_jobs_task::process_jobs(ctx).await {
+            error!(error = ?e, "Failed to process parse jobs");
+        }
+    }
+
     Ok(())
 }
 
-async fn check_server_tasks(
-    ctx: &SchedulerContext,
-    server: &servers_db::DiscordServer,
-) -> Result<(), Error> {
-    if server.is_sub_reload == 1 {
-        check_reload_task(ctx, server).await?;
-    }
+struct DueTasks<'a> {
+    server: &'a servers_db::DiscordServer,
+    weekly: bool,
+    monthly: bool,
+}
 
-    if server.is_sub_week == 1 {
-        check_leaderboard_week_task(ctx, server).await?;
+async fn publish_due_leaderboards(ctx: &SchedulerContext, tasks: &DueTasks<'_>) -> Result<(), Error> {
+    let server = tasks.server;
+
+    if tasks.weekly {
+        info!(
+            server_id = server.server_id,
+            server_name = ?server.server_name,
+            "Publishing weekly leaderboard"
+        );
+        leaderboard_task::publish_leaderboard(ctx, server, LeaderboardDuration::Week).await?;
     }
 
-    if server.is_sub_month == 1 {
-        check_leaderboard_month_task(ctx, server).await?;
+    if tasks.monthly {
+        info!(
+            server_id = server.server_id,
+            server_name = ?server.server_name,
+            "Publishing monthly leaderboard"
+        );
+        leaderboard_task::publish_leaderboard(ctx, server, LeaderboardDuration::Month).await?;
     }
 
     Ok(())
 }
 
-async fn check_reload_task(
-    ctx: &SchedulerContext,
-    server: &servers_db::DiscordServer,
-) -> Result<(), Error> {
+fn is_reload_due(ctx: &SchedulerContext) -> bool {
     if !ctx.config.scheduler.auto_reload.enabled {
-        return Ok(());
+        return false;
     }
 
     if !is_in_reload_window(ctx) {
-        return Ok(());
+        return false;
     }
 
     let utc_now = Utc::now();
     let current_minute = utc_now.minute() as u64;
     let interval = ctx.config.scheduler.auto_reload.interval_minutes;
 
-    if current_minute % interval != 0 {
-        return Ok(());
-    }
-
-    info!(
-        server_id = server.server_id,
-        server_name = ?server.server_name,
-        "Performing scheduled reload"
-    );
+    current_minute % interval == 0
+}
 
-    reload_task::auto_reload(ctx, server).await?;
+fn is_hero_sync_due(ctx: &SchedulerContext) -> bool {
+    let interval = ctx.config.scheduler.hero_sync.interval_hours.max(1);
+    let utc_now = Utc::now();
+    utc_now.minute() == 0 && utc_now.hour() % interval == 0
+}
 
-    Ok(())
+fn is_backup_due(ctx: &SchedulerContext) -> bool {
+    let utc_now = Utc::now();
+    utc_now.minute() == 0 && utc_now.hour() == ctx.config.scheduler.backup.hour
 }
 
-async fn check_leaderboard_week_task(
-    ctx: &SchedulerContext,
-    server: &servers_db::DiscordServer,
-) -> Result<(), Error> {
+fn is_weekly_leaderboard_due(ctx: &SchedulerContext, server: &servers_db::DiscordServer) -> bool {
     let config = &ctx.config.scheduler.weekly_leaderboard;
 
     if !config.enabled {
-        return Ok(());
+        return false;
     }
 
     let target_day = match server.weekly_day {
         Some(d) => d as u32,
-        None => return Ok(()),
+        None => return false,
     };
     let target_hour = match server.weekly_hour {
         Some(h) => h as u32,
-        None => return Ok(()),
+        None => return false,
     };
     let target_minute = config.minute as u32;
 
@@ -149,39 +217,23 @@ async fn check_leaderboard_week_task(
         "Checking weekly leaderboard schedule"
     );
 
-    if weekday != target_day || hour != target_hour || minute != target_minute {
-        return Ok(());
-    }
-
-    info!(
-        server_id = server.server_id,
-        server_name = ?server.server_name,
-        "Publishing weekly leaderboard"
-    );
-
-    reload_task::auto_reload(ctx, server).await?;
-    leaderboard_task::publish_leaderboard(ctx, server, LeaderboardDuration::Week).await?;
-
-    Ok(())
+    weekday == target_day && hour == target_hour && minute == target_minute
 }
 
-async fn check_leaderboard_month_task(
-    ctx: &SchedulerContext,
-    server: &servers_db::DiscordServer,
-) -> Result<(), Error> {
+fn is_monthly_leaderboard_due(ctx: &SchedulerContext, server: &servers_db::DiscordServer) -> bool {
     let config = &ctx.config.scheduler.monthly_leaderboard;
 
     if !config.enabled {
-        return Ok(());
+        return false;
     }
 
     let target_hour = match server.monthly_hour {
         Some(h) => h as u32,
-        None => return Ok(()),
+        None => return false,
     };
     let (target_week, target_weekday) = match (server.monthly_week, server.monthly_weekday) {
         (Some(w), Some(wd)) => (w, wd),
-        _ => return Ok(()),
+        _ => return false,
     };
     let target_minute = config.minute as u32;
 
@@ -190,23 +242,10 @@ async fn check_leaderboard_month_task(
     let minute = utc_now.minute();
 
     if hour != target_hour || minute != target_minute {
-        return Ok(());
-    }
-
-    if !is_nth_weekday_of_month(utc_now, target_week, target_weekday) {
-        return Ok(());
+        return false;
     }
 
-    info!(
-        server_id = server.server_id,
-        server_name = ?server.server_name,
-        "Publishing monthly leaderboard"
-    );
-
-    reload_task::auto_reload(ctx, server).await?;
-    leaderboard_task::publish_leaderboard(ctx, server, LeaderboardDuration::Month).await?;
-
-    Ok(())
+    is_nth_weekday_of_month(utc_now, target_week, target_weekday)
 }
 
 fn is_nth_weekday_of_month(date: chrono::DateTime<Utc>, week: i32, weekday: i32) -> bool {
@@ -0,0 +1,12 @@
+use crate::api::parse_requests;
+use crate::scheduler::SchedulerContext;
+use crate::{seq_span, Error};
+
+/// Advances OpenDota replay parse jobs by one step each scheduler tick.
+#[tracing::instrument(level = "info", skip(ctx))]
+pub async fn process_jobs(ctx: &SchedulerContext) -> Result<(), Error> {
+    let span = seq_span!("parse_jobs");
+    let _enter = span.enter();
+
+    parse_requests::process_parse_jobs(&ctx.config.scheduler.parse_jobs).await
+}
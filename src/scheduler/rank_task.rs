@@ -0,0 +1,135 @@
+use std::collections::HashMap;
+
+use chrono::Utc;
+use poise::serenity_prelude as serenity;
+use tracing::{error, info, warn};
+
+use crate::database::rank_history_db::{self, RankChange};
+use crate::database::{command_events_db, player_servers_db, servers_db};
+use crate::leaderboard::medal;
+use crate::scheduler::{leaderboard_task, SchedulerContext};
+use crate::Error;
+
+/// Posts medal and star changes recorded since the last announcement. The announcement event marks
+/// how far history has been handled, so it is only stored once there are new rows to move past. It
+/// is stored even when the server isn't subscribed, so enabling the toggle doesn't replay old
+/// changes, but not when no message could be delivered, so those changes are retried.
+#[tracing::instrument(level = "info", skip(ctx, server))]
+pub async fn announce_rank_changes(
+    ctx: &SchedulerContext,
+    server: &servers_db::DiscordServer,
+    reload_started_at: i64,
+) -> Result<(), Error> {
+    let since = command_events_db::query_last_event(
+        server.server_id,
+        command_events_db::EventType::RankAnnouncement,
+        None,
+    )
+    .await?
+    .map(|e| e.event_time)
+    .unwrap_or(reload_started_at);
+
+    let players = player_servers_db::query_server_players(server.server_id).await?;
+    let player_ids: Vec<i64> = players.iter().map(|p| p.player_id).collect();
+    if !rank_history_db::has_ranks_since(player_ids, since).await? {
+        return Ok(());
+    }
+
+    if server.is_sub_rank == 1 {
+        post_rank_changes(ctx, server, &players, since).await?;
+    }
+
+    command_events_db::insert_event(
+        server.server_id,
+        command_events_db::EventType::RankAnnouncement,
+        0,
+        Utc::now().timestamp(),
+    )
+    .await?;
+
+    Ok(())
+}
+
+async fn post_rank_changes(
+    ctx: &SchedulerContext,
+    server: &servers_db::DiscordServer,
+    players: &[player_servers_db::PlayerServerModel],
+    since: i64,
+) -> Result<(), Error> {
+    let Some(channel_id_value) = server.channel_id else {
+        warn!(
+            server_id = server.server_id,
+            server_name = ?server.server_name,
+            "Server has no channel_id configured, skipping rank announcements"
+        );
+        return Ok(());
+    };
+
+    let names: HashMap<i64, String> = players
+        .iter()
+        .map(|p| {
+            let name = p.player_name.clone().unwrap_or_else(|| p.discord_name.clone());
+            (p.player_id, name)
+        })
+        .collect();
+
+    let changes =
+        rank_history_db::query_rank_changes(names.keys().copied().collect(), since).await?;
+    if changes.is_empty() {
+        return Ok(());
+    }
+
+    let lines: Vec<String> = changes
+        .iter()
+        .filter_map(|change| {
+            names
+                .get(&change.player_id)
+                .map(|name| format_rank_change(name, change))
+        })
+        .collect();
+
+    let channel_id = serenity::ChannelId::new(channel_id_value as u64);
+    let batches = leaderboard_task::batch_contents(lines, ctx.config.max_message_length);
+    let total = batches.len();
+    let mut delivered = 0;
+    for batch in batches {
+        let message = serenity::CreateMessage::default().content(batch);
+        match channel_id.send_message(&ctx.http, message).await {
+            Ok(_) => delivered += 1,
+            Err(e) => error!(
+                server_id = server.server_id,
+                channel_id = channel_id_value,
+                error = ?e,
+                "Failed to send rank announcement"
+            ),
+        }
+    }
+    // Nothing went out, so leave the last announcement in place and retry these changes next run.
+    // After a partial delivery, retrying would repeat the batches that made it.
+    if delivered == 0 && total > 0 {
+        return Err("Failed to deliver any rank announcement".into());
+    }
+
+    info!(
+        server_id = server.server_id,
+        changes = changes.len(),
+        "Announced rank changes"
+    );
+    Ok(())
+}
+
+/// e.g. `**Nerd** climbed to <:ancient:..> **Ancient 2**`.
+fn format_rank_change(name: &str, change: &RankChange) -> String {
+    let verb = if change.rank_tier > change.previous_rank_tier {
+        "climbed to"
+    } else {
+        "dropped to"
+    };
+    format!(
+        "**{}** {} {} **{}**\n",
+        name,
+        verb,
+        medal::medal_emoji(change.rank_tier),
+        medal::medal_name(change.rank_tier, change.leaderboard_rank)
+    )
+}
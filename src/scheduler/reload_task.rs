@@ -1,46 +1,98 @@
+use std::collections::HashSet;
+
 use chrono::Utc;
-use tracing::info;
+use tracing::{error, info};
 
-use crate::api::api_wrapper::{self, ReloadPlayerStat};
+use crate::api::api_wrapper::{ParallelReload, SyncMode};
+use crate::api::{match_source, open_dota_client};
 use crate::database::{command_events_db, player_servers_db, servers_db};
-use crate::scheduler::SchedulerContext;
+use crate::scheduler::{rank_task, SchedulerContext};
 use crate::{seq_span, Error};
 
-#[tracing::instrument(level = "info", skip(_ctx, server))]
+/// Reloads every player registered in `servers`, fetching players shared between servers only once
+/// per match source, then marks each server as refreshed.
+#[tracing::instrument(level = "info", skip(ctx, servers), fields(server_count = servers.len()))]
 pub async fn auto_reload(
-    _ctx: &SchedulerContext,
-    server: &servers_db::DiscordServer,
+    ctx: &SchedulerContext,
+    servers: &[&servers_db::DiscordServer],
 ) -> Result<(), Error> {
-    // let span = info_span!("auto_reload",
-    // span_name = "auto_reload",
-    // server_id = server.server_id, name = %server.server_name);
     let span = seq_span!("auto_reload");
     let _enter = span.enter();
 
     info!("About to fetch players");
-    reload_players(server).await?;
+    let reload_started_at = Utc::now().timestamp();
+    let skipped = reload_players(servers, ctx.config.opendota.reload_concurrency).await?;
+
+    for server in servers.iter().filter(|s| !skipped.contains(&s.server_id)) {
+        command_events_db::insert_event(
+            server.server_id,
+            command_events_db::EventType::AdminRefresh,
+            0,
+            Utc::now().timestamp(),
+        )
+        .await?;
 
-    command_events_db::insert_event(
-        server.server_id,
-        command_events_db::EventType::AdminRefresh,
-        0,
-        Utc::now().timestamp(),
-    )
-    .await?;
+        if let Err(e) = rank_task::announce_rank_changes(ctx, server, reload_started_at).await {
+            error!(
+                error = ?e,
+                server_id = server.server_id,
+                server_name = ?server.server_name,
+                "Failed to announce rank changes"
+            );
+        }
+    }
 
     Ok(())
 }
 
-async fn reload_players(server: &servers_db::DiscordServer) -> Result<(), Error> {
-    info!(server_id = server.server_id, "Reloading players for server");
-    let players = player_servers_db::query_server_players(server.server_id).await?;
+/// Returns the servers whose players couldn't be collected, which aren't marked as refreshed.
+async fn reload_players(
+    servers: &[&servers_db::DiscordServer],
+    concurrency: usize,
+) -> Result<HashSet<i64>, Error> {
+    // Servers can use different match sources, so a shared player is fetched once per source.
+    let mut seen = HashSet::new();
+    let mut players = Vec::new();
+    let mut registrations = 0;
+    let mut skipped = HashSet::new();
+    for server in servers {
+        info!(server_id = server.server_id, "Collecting players for server");
+        let server_players = match player_servers_db::query_server_players(server.server_id).await {
+            Ok(server_players) => server_players,
+            Err(e) => {
+                error!(
+                    error = ?e,
+                    server_id = server.server_id,
+                    server_name = ?server.server_name,
+                    "Failed to collect players for server, skipping it"
+                );
+                skipped.insert(server.server_id);
+                continue;
+            }
+        };
+        let source = match_source::source_for_server(server.server_id).name();
+        registrations += server_players.len();
+        players.extend(
+            server_players
+                .into_iter()
+                .filter(|p| seen.insert((p.player_id, source))),
+        );
+    }
 
     if players.is_empty() {
         info!("No players registered, skipping auto-reload");
-        return Ok(());
+        return Ok(skipped);
     }
 
-    let stats = reload_all_players(players).await;
+    info!(
+        registrations,
+        distinct_reloads = players.len(),
+        "Reloading distinct players per match source across servers"
+    );
+
+    let stats = ParallelReload::spawn(players, SyncMode::Incremental, concurrency)
+        .collect()
+        .await;
 
     let success_count = stats
         .iter()
@@ -52,23 +104,16 @@ async fn reload_players(server: &servers_db::DiscordServer) -> Result<(), Error>
         .filter(|s| matches!(s.result, Ok(None)))
         .count();
 
+    info!(success_count, failure_count, removed_count, "Completed auto-reload");
+
+    let metrics = open_dota_client::metrics();
     info!(
-        success_count,
-        failure_count, removed_count, server.server_name, "Completed auto-reload for server"
+        requests = metrics.requests,
+        retries = metrics.retries,
+        rate_limited = metrics.rate_limited,
+        failures = metrics.failures,
+        "OpenDota client totals"
     );
 
-    Ok(())
-}
-
-async fn reload_all_players(
-    players: Vec<player_servers_db::PlayerServerModel>,
-) -> Vec<ReloadPlayerStat> {
-    let mut stats = Vec::new();
-
-    for player in players {
-        let stat = api_wrapper::reload_player(&player).await;
-        stats.push(stat);
-    }
-
-    stats
+    Ok(skipped)
 }